@@ -9,7 +9,10 @@ use std::{
 use deranged::RangedU8;
 use strum::FromRepr;
 
-use crate::{card::Suit, error::ParseError};
+use crate::{
+    card::Suit,
+    error::{BidError, ParseError},
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum BiddingSuit {
@@ -17,6 +20,21 @@ pub enum BiddingSuit {
     NoTrumps,
 }
 
+impl BiddingSuit {
+    pub fn trumps(&self) -> Option<Suit> {
+        match self {
+            Self::Suit(suit) => Some(*suit),
+            Self::NoTrumps => None,
+        }
+    }
+}
+
+impl From<Suit> for BiddingSuit {
+    fn from(value: Suit) -> Self {
+        Self::Suit(value)
+    }
+}
+
 impl FromStr for BiddingSuit {
     type Err = ParseError;
 
@@ -133,6 +151,19 @@ pub enum Seat {
     West,
 }
 
+impl Seat {
+    pub fn side(&self) -> Side {
+        match self {
+            Self::North | Self::South => Side::NorthSouth,
+            Self::East | Self::West => Side::EastWest,
+        }
+    }
+
+    pub fn partner(&self) -> Self {
+        *self + 2
+    }
+}
+
 impl Add<usize> for Seat {
     type Output = Self;
 
@@ -166,7 +197,7 @@ impl FromStr for Seat {
             "east" | "e" => Ok(Self::East),
             "south" | "s" => Ok(Self::South),
             "west" | "w" => Ok(Self::West),
-            _ => Err(ParseError::SuitNotValid),
+            _ => Err(ParseError::SeatNotValid),
         }
     }
 }
@@ -196,6 +227,21 @@ impl TryFrom<char> for Seat {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    NorthSouth = 0,
+    EastWest,
+}
+
+impl Side {
+    pub fn opponents(&self) -> Self {
+        match self {
+            Self::NorthSouth => Self::EastWest,
+            Self::EastWest => Self::NorthSouth,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Status {
     Undoubled,
@@ -210,6 +256,17 @@ pub struct Contract {
     pub declarer: Seat,
 }
 
+impl Display for Contract {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let status = match self.status {
+            Status::Undoubled => "",
+            Status::Doubled => "X",
+            Status::Redoubled => "XX",
+        };
+        write!(f, "{}{} by {}", self.bid, status, char::from(self.declarer))
+    }
+}
+
 #[cfg(test)]
 impl FromStr for Contract {
     type Err = ParseError;
@@ -243,7 +300,7 @@ impl FromStr for Contract {
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Auction {
     dealer: Seat,
-    pub sequence: Vec<AuctionBid>,
+    sequence: Vec<AuctionBid>,
 }
 
 impl Default for Auction {
@@ -327,11 +384,25 @@ impl Auction {
                 AuctionBid::Double => status = max(status, Status::Doubled),
                 AuctionBid::Redouble => status = max(status, Status::Redoubled),
                 AuctionBid::Bid(bid) => {
+                    // The declarer is whichever player of the side that won the auction
+                    // first named the final strain.
+                    let side = (self.turn() - idx - 1).side();
+                    let declarer = self
+                        .sequence
+                        .iter()
+                        .enumerate()
+                        .map(|(position, call)| (self.dealer + position, call))
+                        .find(|(seat, call)| {
+                            seat.side() == side
+                                && matches!(call, AuctionBid::Bid(named) if named.suit == bid.suit)
+                        })
+                        .map(|(seat, _)| seat)?;
+
                     return Some(Contract {
                         bid: *bid,
                         status,
-                        declarer: self.turn() - idx - 1,
-                    })
+                        declarer,
+                    });
                 }
             }
         }
@@ -342,7 +413,55 @@ impl Auction {
         &self.dealer
     }
 
-    pub fn enter_bid(&mut self, bid: AuctionBid) -> Result<(), String> {
+    pub fn sequence(&self) -> &[AuctionBid] {
+        &self.sequence
+    }
+
+    /// Returns the last call in the auction other than a pass, along with whether it was
+    /// made by the side whose turn it is to call.
+    fn last_call(&self) -> Option<(&AuctionBid, bool)> {
+        let len = self.sequence.len();
+        self.sequence
+            .iter()
+            .enumerate()
+            .rev()
+            .find(|(_, bid)| **bid != AuctionBid::Pass)
+            .map(|(idx, bid)| (bid, (len - idx).is_multiple_of(2)))
+    }
+
+    /// Enters the next call in the auction, on behalf of the seat whose turn it is.
+    ///
+    /// Calls which are not permitted by the laws of bidding are rejected, leaving the
+    /// auction unchanged.
+    pub fn enter_bid(&mut self, bid: AuctionBid) -> Result<(), BidError> {
+        if self.closed() {
+            return Err(BidError::AuctionClosed);
+        }
+
+        match bid {
+            AuctionBid::Pass => {}
+            AuctionBid::Bid(contract_bid) => {
+                if self
+                    .contract()
+                    .is_some_and(|contract| contract.bid >= contract_bid)
+                {
+                    return Err(BidError::InsufficientBid);
+                }
+            }
+            AuctionBid::Double => match self.last_call() {
+                None => return Err(BidError::NothingToDouble),
+                Some((AuctionBid::Bid(_), true)) => return Err(BidError::DoubleOfPartner),
+                Some((AuctionBid::Bid(_), false)) => {}
+                Some(_) => return Err(BidError::AlreadyDoubled),
+            },
+            AuctionBid::Redouble => match self.last_call() {
+                Some((AuctionBid::Double, false)) => {}
+                Some((AuctionBid::Double, true)) => return Err(BidError::RedoubleOfPartner),
+                Some((AuctionBid::Redouble, _)) => return Err(BidError::AlreadyRedoubled),
+                _ => return Err(BidError::NothingToRedouble),
+            },
+        }
+
         self.sequence.push(bid);
         Ok(())
     }
@@ -382,23 +501,23 @@ mod test {
     }
 
     fn game_with_small_interference() -> Auction {
-        let mut auction = Auction::new(Seat::South);
-        auction.sequence.append(&mut vec![AuctionBid::Pass; 2]);
-        auction.sequence.push("1D".parse().unwrap());
-        auction.sequence.push("1H".parse().unwrap());
-        auction.sequence.push("1NT".parse().unwrap());
-        auction.sequence.push(AuctionBid::Pass);
-        auction.sequence.push("2NT".parse().unwrap());
-        auction.sequence.push(AuctionBid::Pass);
-        auction.sequence.push("3NT".parse().unwrap());
-        auction.sequence.append(&mut vec![AuctionBid::Pass; 3]);
-
-        auction
+        auction_from(
+            Seat::South,
+            &[
+                "P", "P", "1D", "1H", "1NT", "P", "2NT", "P", "3NT", "P", "P", "P",
+            ],
+        )
     }
 
     fn three_passes() -> Auction {
-        let mut auction = Auction::new(Seat::West);
-        auction.sequence.append(&mut vec![AuctionBid::Pass; 3]);
+        auction_from(Seat::West, &["P", "P", "P"])
+    }
+
+    fn auction_from(dealer: Seat, bids: &[&str]) -> Auction {
+        let mut auction = Auction::new(dealer);
+        for bid in bids {
+            auction.enter_bid(bid.parse().unwrap()).unwrap();
+        }
 
         auction
     }
@@ -428,6 +547,7 @@ mod test {
     }
 
     #[test]
+    #[allow(clippy::neg_cmp_op_on_partial_ord)]
     fn auction_bid_ordering() {
         assert!("6H".parse::<AuctionBid>().unwrap() > "4NT".parse().unwrap());
 
@@ -461,6 +581,22 @@ mod test {
         assert_eq!(Seat::East - Seat::West, Seat::West - Seat::East);
     }
 
+    #[test]
+    fn seat_sides() {
+        assert_eq!(Seat::South, Seat::North.partner());
+        assert_eq!(Seat::East, Seat::West.partner());
+        assert_eq!(Side::NorthSouth, Seat::South.side());
+        assert_eq!(Side::EastWest, Seat::East.side());
+        assert_eq!(Side::NorthSouth, Seat::West.side().opponents());
+    }
+
+    #[test]
+    fn seat_parsing() {
+        assert_eq!(Ok(Seat::East), "east".parse());
+        assert_eq!(Ok(Seat::West), "W".parse());
+        assert_eq!(Err(ParseError::SeatNotValid), "x".parse::<Seat>());
+    }
+
     #[test]
     fn auction_by_seat() {
         let auction = game_with_small_interference();
@@ -504,6 +640,18 @@ mod test {
 
         assert!(!three_passes.closed());
         assert_eq!(None, three_passes.contract());
+
+        let raised = auction_from(Seat::North, &["1H", "2C", "4H", "X", "P", "P", "P"]);
+        assert_eq!(Some("N:4HX".parse().unwrap()), raised.contract());
+
+        let transferred = auction_from(Seat::East, &["1NT", "P", "2H", "P", "2S", "P", "P", "P"]);
+        assert_eq!(Some("E:2S".parse().unwrap()), transferred.contract());
+
+        let converted = auction_from(
+            Seat::East,
+            &["1S", "P", "2S", "P", "2NT", "P", "3NT", "P", "P", "P"],
+        );
+        assert_eq!(Some("E:3NT".parse().unwrap()), converted.contract());
     }
 
     #[test]
@@ -530,4 +678,102 @@ mod test {
             three_passes().to_string()
         );
     }
+
+    #[test]
+    fn sufficient_bids() {
+        let mut auction = auction_from(Seat::North, &["1H", "P"]);
+
+        assert_eq!(
+            Err(BidError::InsufficientBid),
+            auction.enter_bid("1D".parse().unwrap())
+        );
+        assert_eq!(
+            Err(BidError::InsufficientBid),
+            auction.enter_bid("1H".parse().unwrap())
+        );
+        assert_eq!(Ok(()), auction.enter_bid("1S".parse().unwrap()));
+        assert_eq!(Ok(()), auction.enter_bid("2C".parse().unwrap()));
+        assert_eq!(3 + 1, auction.sequence().len());
+    }
+
+    #[test]
+    fn doubles() {
+        let mut auction = Auction::new(Seat::East);
+        assert_eq!(
+            Err(BidError::NothingToDouble),
+            auction.enter_bid(AuctionBid::Double)
+        );
+
+        let mut auction = auction_from(Seat::East, &["1S", "P"]);
+        assert_eq!(
+            Err(BidError::DoubleOfPartner),
+            auction.enter_bid(AuctionBid::Double)
+        );
+
+        let mut auction = auction_from(Seat::East, &["1S"]);
+        assert_eq!(Ok(()), auction.enter_bid(AuctionBid::Double));
+        assert_eq!(
+            Err(BidError::AlreadyDoubled),
+            auction.enter_bid(AuctionBid::Double)
+        );
+
+        let mut auction = auction_from(Seat::East, &["1S", "X", "P"]);
+        assert_eq!(
+            Err(BidError::AlreadyDoubled),
+            auction.enter_bid(AuctionBid::Double)
+        );
+
+        let mut auction = auction_from(Seat::East, &["1S", "P", "P"]);
+        assert_eq!(Ok(()), auction.enter_bid(AuctionBid::Double));
+    }
+
+    #[test]
+    fn redoubles() {
+        let mut auction = auction_from(Seat::West, &["1C"]);
+        assert_eq!(
+            Err(BidError::NothingToRedouble),
+            auction.enter_bid(AuctionBid::Redouble)
+        );
+
+        let mut auction = auction_from(Seat::West, &["1C", "X", "P"]);
+        assert_eq!(
+            Err(BidError::RedoubleOfPartner),
+            auction.enter_bid(AuctionBid::Redouble)
+        );
+
+        let mut auction = auction_from(Seat::West, &["1C", "X"]);
+        assert_eq!(Ok(()), auction.enter_bid(AuctionBid::Redouble));
+        assert_eq!(
+            Err(BidError::AlreadyRedoubled),
+            auction.enter_bid(AuctionBid::Redouble)
+        );
+        assert_eq!(
+            Err(BidError::AlreadyDoubled),
+            auction.enter_bid(AuctionBid::Double)
+        );
+
+        let mut auction = auction_from(Seat::West, &["1C", "X", "P", "P"]);
+        assert_eq!(Ok(()), auction.enter_bid(AuctionBid::Redouble));
+        assert_eq!(
+            Some("W:1CXX".parse::<Contract>().unwrap()),
+            auction.contract()
+        );
+    }
+
+    #[test]
+    fn closed_auctions() {
+        let mut passed_out = auction_from(Seat::North, &["P", "P", "P", "P"]);
+        assert!(passed_out.closed());
+        assert_eq!(
+            Err(BidError::AuctionClosed),
+            passed_out.enter_bid("1C".parse().unwrap())
+        );
+
+        let mut game = game_with_small_interference();
+        assert_eq!(
+            Err(BidError::AuctionClosed),
+            game.enter_bid(AuctionBid::Pass)
+        );
+        assert_eq!(12, game.sequence().len());
+    }
 }
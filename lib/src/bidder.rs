@@ -0,0 +1,792 @@
+//! Robot bidding: the [`Bidder`] trait for anything which can choose a call, and
+//! [`StandardAmerican`], a simple natural system.
+
+use crate::{
+    auction::{Auction, AuctionBid, BiddingSuit, ContractBid, Seat},
+    card::Suit,
+    hand::Hand,
+    shape::Shape,
+};
+
+/// Chooses calls for a seat in an auction.
+pub trait Bidder {
+    /// Returns the call to make with `hand` when it's the hand's turn to call in `auction`.
+    fn bid(&self, hand: &Hand, auction: &Auction) -> AuctionBid;
+}
+
+/// A simple natural system in the style of Standard American: five-card majors, a 15-17
+/// no trump, a strong two clubs and weak twos, with limit raises, simple overcalls and
+/// takeout doubles. Later rounds are bid by adding up the points and counting the trumps
+/// which partner's calls have shown. Whenever the system has nothing to say, it passes.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StandardAmerican;
+
+impl Bidder for StandardAmerican {
+    fn bid(&self, hand: &Hand, auction: &Auction) -> AuctionBid {
+        let call = Context::new(hand, auction).call();
+        if auction.clone().enter_bid(call).is_ok() {
+            call
+        } else {
+            AuctionBid::Pass
+        }
+    }
+}
+
+const SUITS: [Suit; 4] = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs];
+
+fn is_major(suit: Suit) -> bool {
+    matches!(suit, Suit::Spades | Suit::Hearts)
+}
+
+/// Returns the bid at the given level and strain, which must be from 1 to 7.
+fn bid(level: u8, strain: BiddingSuit) -> AuctionBid {
+    AuctionBid::suit_bid(level, strain).unwrap()
+}
+
+fn suit_bid(level: u8, suit: Suit) -> AuctionBid {
+    bid(level, BiddingSuit::Suit(suit))
+}
+
+/// Returns the level of a game contract in the given strain.
+fn game_level(strain: BiddingSuit) -> u8 {
+    match strain {
+        BiddingSuit::NoTrumps => 3,
+        BiddingSuit::Suit(suit) if is_major(suit) => 4,
+        BiddingSuit::Suit(_) => 5,
+    }
+}
+
+/// What a hand can see when choosing its call.
+struct Context<'a> {
+    hand: &'a Hand,
+    auction: &'a Auction,
+    seat: Seat,
+    hcp: u8,
+    shape: Shape,
+}
+
+impl<'a> Context<'a> {
+    fn new(hand: &'a Hand, auction: &'a Auction) -> Self {
+        Self {
+            hand,
+            auction,
+            seat: auction.turn(),
+            hcp: hand.hcp(),
+            shape: hand.shape(),
+        }
+    }
+
+    fn len(&self, suit: Suit) -> usize {
+        self.shape.length(suit)
+    }
+
+    fn balanced(&self) -> bool {
+        self.shape.is_balanced()
+    }
+
+    /// Returns the longest suit, the higher ranking of any equally long.
+    fn longest(&self) -> Suit {
+        self.shape.longest()[0]
+    }
+
+    /// Returns whether the hand can expect to win a trick in `suit` when the opponents
+    /// lead it: the ace, the king twice guarded, the queen thrice, or the jack four times.
+    fn stopper(&self, suit: Suit) -> bool {
+        let cards = self.hand.suit(suit);
+        cards
+            .iter()
+            .any(|card| card.rank as usize + cards.len() > 14)
+    }
+
+    /// Returns the calls other than passes made by the given seat.
+    fn calls(&self, seat: Seat) -> Vec<AuctionBid> {
+        self.auction
+            .bids_for(seat)
+            .into_iter()
+            .filter(|call| **call != AuctionBid::Pass)
+            .copied()
+            .collect()
+    }
+
+    /// Returns the opening bid and the seat which made it, if anyone has opened.
+    fn opening(&self) -> Option<(Seat, ContractBid)> {
+        let dealer = *self.auction.dealer();
+        self.auction
+            .sequence()
+            .iter()
+            .enumerate()
+            .find_map(|(idx, call)| match call {
+                AuctionBid::Bid(bid) => Some((dealer + idx, *bid)),
+                _ => None,
+            })
+    }
+
+    /// Returns the highest bid so far.
+    fn current(&self) -> Option<ContractBid> {
+        self.auction.contract().map(|contract| contract.bid)
+    }
+
+    /// Returns the lowest bid in `strain` which is sufficient, if any.
+    fn cheapest(&self, strain: BiddingSuit) -> Option<u8> {
+        let level = match self.current() {
+            None => 1,
+            Some(current) if current.suit < strain => u8::from(current.level),
+            Some(current) => u8::from(current.level) + 1,
+        };
+        (level <= 7).then_some(level)
+    }
+
+    /// Returns the lowest sufficient bid in `strain`, `jump` levels higher, or a pass if
+    /// there is no such bid.
+    fn cheapest_bid(&self, strain: BiddingSuit, jump: u8) -> AuctionBid {
+        match self.cheapest(strain) {
+            Some(level) if level + jump <= 7 => bid(level + jump, strain),
+            _ => AuctionBid::Pass,
+        }
+    }
+
+    fn call(&self) -> AuctionBid {
+        let Some((opener, opening)) = self.opening() else {
+            return self.open();
+        };
+        let partner = self.seat.partner();
+        let mine = self.calls(self.seat);
+        let partners = self.calls(partner);
+
+        if opener == self.seat {
+            match (mine.len(), partners.first()) {
+                (1, Some(AuctionBid::Bid(response))) if partners.len() == 1 => {
+                    self.rebid(opening, *response)
+                }
+                _ => self.place(),
+            }
+        } else if opener == partner {
+            if mine.is_empty() {
+                self.respond(opening)
+            } else {
+                self.place()
+            }
+        } else if mine.is_empty() {
+            match partners.first() {
+                None => self.compete(),
+                Some(AuctionBid::Double) => self.advance_double(),
+                Some(AuctionBid::Bid(overcall)) => self.advance(*overcall),
+                Some(_) => AuctionBid::Pass,
+            }
+        } else {
+            self.place()
+        }
+    }
+
+    /// Returns the suit to open at the one level: the longer major with five or more
+    /// cards, or else the longer minor, diamonds with four cards in each and clubs with
+    /// three.
+    fn opening_suit(&self) -> Suit {
+        let [spades, hearts, diamonds, clubs] = self.shape.lengths();
+        if spades >= 5 && spades >= hearts {
+            Suit::Spades
+        } else if hearts >= 5 {
+            Suit::Hearts
+        } else if diamonds >= 4 && diamonds >= clubs {
+            Suit::Diamonds
+        } else {
+            Suit::Clubs
+        }
+    }
+
+    fn open(&self) -> AuctionBid {
+        let passes = self.auction.sequence().len();
+        match self.hcp {
+            22.. => return suit_bid(2, Suit::Clubs),
+            20..=21 if self.balanced() => return bid(2, BiddingSuit::NoTrumps),
+            15..=17 if self.balanced() => return bid(1, BiddingSuit::NoTrumps),
+            _ => {}
+        }
+
+        let opens = if passes == 3 {
+            self.hand.rule_of_15()
+        } else {
+            self.hcp >= 12 || (self.hcp >= 10 && self.hand.rule_of_20())
+        };
+        if opens {
+            return suit_bid(1, self.opening_suit());
+        }
+
+        // Weak twos and three-level preempts, which are pointless in fourth seat.
+        let longest = self.longest();
+        if passes < 3 && (5..=10).contains(&self.hcp) {
+            match self.len(longest) {
+                7.. => return suit_bid(3, longest),
+                6 if longest != Suit::Clubs => return suit_bid(2, longest),
+                _ => {}
+            }
+        }
+        AuctionBid::Pass
+    }
+
+    fn respond(&self, opening: ContractBid) -> AuctionBid {
+        match (u8::from(opening.level), opening.suit) {
+            (1, BiddingSuit::NoTrumps) => self.respond_to_no_trumps(),
+            (1, BiddingSuit::Suit(suit)) => self.respond_to_suit(suit),
+            (2, BiddingSuit::Suit(Suit::Clubs)) => self.cheapest_bid(Suit::Diamonds.into(), 0),
+            (2, BiddingSuit::NoTrumps) => match self.hcp {
+                0..=3 => AuctionBid::Pass,
+                4..=10 => bid(3, BiddingSuit::NoTrumps),
+                11..=12 => bid(4, BiddingSuit::NoTrumps),
+                _ => bid(6, BiddingSuit::NoTrumps),
+            },
+            (_, BiddingSuit::Suit(suit)) => self.respond_to_preempt(suit),
+            _ => AuctionBid::Pass,
+        }
+    }
+
+    /// Returns the five-card or longer major to show opposite a no trump opening, if any.
+    fn long_major(&self) -> Option<Suit> {
+        let [spades, hearts, ..] = self.shape.lengths();
+        if spades >= 5 && spades >= hearts {
+            Some(Suit::Spades)
+        } else if hearts >= 5 {
+            Some(Suit::Hearts)
+        } else {
+            None
+        }
+    }
+
+    fn respond_to_no_trumps(&self) -> AuctionBid {
+        let major = self.long_major();
+        match (self.hcp, major) {
+            (0..=7, Some(major)) => self.cheapest_bid(major.into(), 0),
+            (0..=7, None) => AuctionBid::Pass,
+            (8..=9, _) => bid(2, BiddingSuit::NoTrumps),
+            (10..=15, Some(major)) if self.len(major) >= 6 => suit_bid(4, major),
+            (10..=15, Some(major)) => suit_bid(3, major),
+            (10..=15, None) => bid(3, BiddingSuit::NoTrumps),
+            (16..=17, _) => bid(4, BiddingSuit::NoTrumps),
+            _ => bid(6, BiddingSuit::NoTrumps),
+        }
+    }
+
+    fn respond_to_suit(&self, opened: Suit) -> AuctionBid {
+        if self.hcp < 6 {
+            return AuctionBid::Pass;
+        }
+
+        let support = self.len(opened);
+        if is_major(opened) && support >= 3 {
+            return match self.hcp {
+                6..=9 => suit_bid(2, opened),
+                10..=12 => suit_bid(3, opened),
+                _ => suit_bid(4, opened),
+            };
+        }
+
+        // A new suit at the one level: the longest of five cards or more, or else the
+        // cheapest of four.
+        let higher: Vec<Suit> = SUITS
+            .into_iter()
+            .filter(|suit| *suit > opened && self.len(*suit) >= 4)
+            .collect();
+        let five = higher.iter().copied().find(|suit| self.len(*suit) >= 5);
+        if let Some(suit) = five.or(higher.last().copied()) {
+            return self.cheapest_bid(suit.into(), 0);
+        }
+
+        // A new suit at the two level needs ten points, and five cards for hearts.
+        let longest = self.longest();
+        let needed = if longest == Suit::Hearts { 5 } else { 4 };
+        if self.hcp >= 10 && longest < opened && self.len(longest) >= needed {
+            return self.cheapest_bid(longest.into(), 0);
+        }
+
+        if support >= 5 {
+            match self.hcp {
+                6..=9 => return suit_bid(2, opened),
+                10..=12 => return suit_bid(3, opened),
+                _ => {}
+            }
+        }
+
+        match self.hcp {
+            6..=10 => bid(1, BiddingSuit::NoTrumps),
+            11..=12 if self.balanced() => bid(2, BiddingSuit::NoTrumps),
+            13.. if self.balanced() => bid(3, BiddingSuit::NoTrumps),
+            _ => self.cheapest_bid(self.longest().into(), 0),
+        }
+    }
+
+    fn respond_to_preempt(&self, opened: Suit) -> AuctionBid {
+        let support = self.len(opened) >= 3;
+        match self.hcp {
+            16.. if support && is_major(opened) => suit_bid(4, opened),
+            16.. if self.balanced() && self.stopper_outside(opened) => {
+                self.cheapest_bid(BiddingSuit::NoTrumps, 0)
+            }
+            _ if support => self.cheapest_bid(opened.into(), 0),
+            _ => AuctionBid::Pass,
+        }
+    }
+
+    /// Returns whether the hand stops every suit but `suit`.
+    fn stopper_outside(&self, suit: Suit) -> bool {
+        SUITS
+            .into_iter()
+            .filter(|other| *other != suit)
+            .all(|other| self.stopper(other))
+    }
+
+    /// Returns the opener's second call, after partner's first response.
+    fn rebid(&self, opening: ContractBid, response: ContractBid) -> AuctionBid {
+        let level = u8::from(response.level);
+        match (u8::from(opening.level), opening.suit) {
+            (1, BiddingSuit::NoTrumps) => match (level, response.suit) {
+                (2, BiddingSuit::NoTrumps) if self.hcp >= 16 => bid(3, BiddingSuit::NoTrumps),
+                (3, BiddingSuit::Suit(major)) if is_major(major) => {
+                    if self.len(major) >= 3 {
+                        suit_bid(4, major)
+                    } else {
+                        bid(3, BiddingSuit::NoTrumps)
+                    }
+                }
+                (4, BiddingSuit::NoTrumps) if self.hcp >= 17 => bid(6, BiddingSuit::NoTrumps),
+                _ => AuctionBid::Pass,
+            },
+            (2, BiddingSuit::NoTrumps) => match (level, response.suit) {
+                (4, BiddingSuit::NoTrumps) if self.hcp >= 21 => bid(6, BiddingSuit::NoTrumps),
+                _ => AuctionBid::Pass,
+            },
+            (2, BiddingSuit::Suit(Suit::Clubs)) => {
+                if self.balanced() && self.hcp <= 24 {
+                    self.cheapest_bid(BiddingSuit::NoTrumps, 0)
+                } else if self.balanced() {
+                    bid(3, BiddingSuit::NoTrumps)
+                } else {
+                    self.cheapest_bid(self.longest().into(), 0)
+                }
+            }
+            (1, BiddingSuit::Suit(opened)) => self.rebid_suit(opened, response),
+            _ => AuctionBid::Pass,
+        }
+    }
+
+    fn rebid_suit(&self, opened: Suit, response: ContractBid) -> AuctionBid {
+        let level = u8::from(response.level);
+        let no_trumps = BiddingSuit::NoTrumps;
+        let game = |strain: BiddingSuit| bid(game_level(strain), strain);
+        // Game in the opened suit if a major, or else in no trumps.
+        let opened_game = if is_major(opened) {
+            game(opened.into())
+        } else {
+            game(no_trumps)
+        };
+
+        let responded = match response.suit {
+            BiddingSuit::Suit(suit) if suit == opened => {
+                return match (level, self.hcp) {
+                    (2, 0..=14) => AuctionBid::Pass,
+                    (2, 15..=17) => suit_bid(3, opened),
+                    (2, _) => opened_game,
+                    (3, 14..) => opened_game,
+                    _ => AuctionBid::Pass,
+                };
+            }
+            BiddingSuit::NoTrumps => {
+                return match (level, self.hcp) {
+                    (1, 18..=19) if self.balanced() => bid(2, no_trumps),
+                    (1, _) if self.balanced() => AuctionBid::Pass,
+                    (2, 14..) => opened_game,
+                    (1, _) => self.second_suit(opened).unwrap_or(suit_bid(2, opened)),
+                    _ => AuctionBid::Pass,
+                };
+            }
+            BiddingSuit::Suit(suit) => suit,
+        };
+
+        // Partner's new suit is forcing. Raise a major with four-card support.
+        if self.len(responded) >= 4 && (is_major(responded) || !is_major(opened)) {
+            return match self.hcp {
+                0..=15 => self.cheapest_bid(responded.into(), 0),
+                16..=18 => self.cheapest_bid(responded.into(), 1),
+                _ if is_major(responded) => suit_bid(4, responded),
+                _ => bid(3, no_trumps),
+            };
+        }
+
+        // Show a four-card major at the one level, hearts before spades.
+        let major = [Suit::Hearts, Suit::Spades]
+            .into_iter()
+            .find(|suit| *suit > responded && *suit != opened && self.len(*suit) >= 4);
+        if let (1, Some(major)) = (level, major) {
+            return suit_bid(1, major);
+        }
+
+        if self.balanced() {
+            return match self.hcp {
+                0..=14 => self.cheapest_bid(no_trumps, 0),
+                15..=19 => self.cheapest_bid(no_trumps, 1),
+                _ => bid(3, no_trumps),
+            };
+        }
+
+        if self.len(opened) >= 6 {
+            return match self.hcp {
+                0..=15 => self.cheapest_bid(opened.into(), 0),
+                16..=18 => self.cheapest_bid(opened.into(), 1),
+                _ => opened_game,
+            };
+        }
+
+        self.second_suit(opened)
+            .unwrap_or(self.cheapest_bid(opened.into(), 0))
+    }
+
+    /// Returns the cheapest bid in a second suit of four cards or more: one lower ranking
+    /// than the suit opened, or with 16 points or more, any.
+    fn second_suit(&self, opened: Suit) -> Option<AuctionBid> {
+        SUITS
+            .into_iter()
+            .filter(|suit| *suit != opened && self.len(*suit) >= 4)
+            .find(|suit| *suit < opened || self.hcp >= 16)
+            .map(|suit| self.cheapest_bid(suit.into(), 0))
+    }
+
+    /// Returns the call over an opponents' opening, when partner hasn't yet called.
+    fn compete(&self) -> AuctionBid {
+        let Some(current) = self.current() else {
+            return AuctionBid::Pass;
+        };
+        let theirs = current.suit.trumps();
+        let level = u8::from(current.level);
+
+        if (15..=18).contains(&self.hcp)
+            && self.balanced()
+            && theirs.is_none_or(|suit| self.stopper(suit))
+            && self.cheapest(BiddingSuit::NoTrumps) == Some(1)
+        {
+            return bid(1, BiddingSuit::NoTrumps);
+        }
+
+        if let Some(theirs) = theirs {
+            let short = self.len(theirs) <= 2;
+            let unbid = SUITS
+                .into_iter()
+                .filter(|suit| *suit != theirs)
+                .all(|suit| self.len(suit) >= 3);
+            if (self.hcp >= 12 && short && unbid) || self.hcp >= 17 {
+                return AuctionBid::Double;
+            }
+        }
+
+        let suit = SUITS
+            .into_iter()
+            .filter(|suit| Some(*suit) != theirs && self.len(*suit) >= 5)
+            .max_by_key(|suit| self.len(*suit));
+        if let Some(suit) = suit {
+            let needed = match self.cheapest(suit.into()) {
+                Some(1) => 8,
+                Some(2) if level <= 2 => 11,
+                _ => return AuctionBid::Pass,
+            };
+            if (needed..=16).contains(&self.hcp) {
+                return self.cheapest_bid(suit.into(), 0);
+            }
+        }
+        AuctionBid::Pass
+    }
+
+    /// Returns the call after partner's takeout double, which must be answered unless the
+    /// next opponent has called.
+    fn advance_double(&self) -> AuctionBid {
+        let Some(current) = self.current() else {
+            return AuctionBid::Pass;
+        };
+        let forced = self.auction.sequence().last() == Some(&AuctionBid::Pass);
+        let theirs = current.suit.trumps();
+
+        // The longest unbid suit, preferring a major.
+        let suit = SUITS
+            .into_iter()
+            .filter(|suit| Some(*suit) != theirs)
+            .max_by_key(|suit| (self.len(*suit), is_major(*suit)))
+            .unwrap();
+        match self.hcp {
+            12.. if is_major(suit) && self.len(suit) >= 4 => suit_bid(4, suit),
+            9.. => self.cheapest_bid(suit.into(), 1),
+            _ if forced || self.hcp >= 6 => self.cheapest_bid(suit.into(), 0),
+            _ => AuctionBid::Pass,
+        }
+    }
+
+    /// Returns the call after partner's overcall.
+    fn advance(&self, overcall: ContractBid) -> AuctionBid {
+        let suit = match overcall.suit {
+            BiddingSuit::NoTrumps if u8::from(overcall.level) == 1 => {
+                return self.respond_to_no_trumps();
+            }
+            BiddingSuit::NoTrumps => return AuctionBid::Pass,
+            BiddingSuit::Suit(suit) => suit,
+        };
+
+        if self.len(suit) >= 3 {
+            return match self.hcp {
+                0..=5 => AuctionBid::Pass,
+                6..=9 => self.cheapest_bid(suit.into(), 0),
+                10..=12 => match self.cheapest(suit.into()) {
+                    Some(level) if level < 3 => suit_bid(3, suit),
+                    _ => self.cheapest_bid(suit.into(), 0),
+                },
+                _ if is_major(suit) => suit_bid(4, suit),
+                _ => self.cheapest_bid(BiddingSuit::NoTrumps, 0),
+            };
+        }
+
+        let longest = self.longest();
+        if self.hcp >= 10 && self.len(longest) >= 5 {
+            return self.cheapest_bid(longest.into(), 0);
+        }
+        AuctionBid::Pass
+    }
+
+    /// Returns the least number of high card points and the least length in each suit
+    /// (spades first) which partner's calls have shown.
+    fn partner_shows(&self) -> (u8, [usize; 4]) {
+        let partner = self.seat.partner();
+        let dealer = *self.auction.dealer();
+        let opener = self.opening().map(|(seat, _)| seat);
+        let mut points = 0;
+        let mut lengths = [0; 4];
+        let mut last_bid = None;
+        let mut partner_bids = 0;
+
+        for (idx, call) in self.auction.sequence().iter().enumerate() {
+            let seat = dealer + idx;
+            let AuctionBid::Bid(bid) = call else {
+                if seat == partner && *call == AuctionBid::Double && partner_bids == 0 {
+                    points = points.max(12);
+                }
+                continue;
+            };
+            let level = u8::from(bid.level);
+            if seat != partner {
+                last_bid = Some((seat, *bid));
+                continue;
+            }
+
+            let ours = last_bid.is_some_and(|(other, _)| other == self.seat);
+            let jump = last_bid.is_some_and(|(_, last)| {
+                level > u8::from(last.level) + u8::from(bid.suit <= last.suit)
+            });
+            let (shown, length) = match (bid.suit, partner_bids) {
+                (BiddingSuit::NoTrumps, 0) if opener == Some(partner) => {
+                    (if level == 1 { 15 } else { 20 }, 0)
+                }
+                (BiddingSuit::NoTrumps, 0) if ours => match level {
+                    1 => (6, 0),
+                    2 => (11, 0),
+                    _ => (13, 0),
+                },
+                (BiddingSuit::NoTrumps, 0) => (15, 0),
+                (BiddingSuit::NoTrumps, _) if opener == Some(partner) => {
+                    (12 + u8::from(jump) * 6, 0)
+                }
+                (BiddingSuit::NoTrumps, _) => (6 + u8::from(jump) * 5, 0),
+                (BiddingSuit::Suit(Suit::Clubs), 0) if opener == Some(partner) && level == 2 => {
+                    (22, 0)
+                }
+                (BiddingSuit::Suit(suit), 0) if opener == Some(partner) => match level {
+                    1 if is_major(suit) => (12, 5),
+                    1 if suit == Suit::Diamonds => (12, 4),
+                    1 => (12, 3),
+                    2 => (5, 6),
+                    _ => (5, 7),
+                },
+                (BiddingSuit::Suit(suit), 0) => {
+                    let raise = last_bid.is_some_and(|(_, last)| last.suit == bid.suit) && ours;
+                    match (raise, level) {
+                        (true, 2) => (6, 3),
+                        (true, 3) => (10, 3),
+                        (true, _) => (13, 3),
+                        (false, 1) => (6, 4),
+                        (false, _) if opener != Some(self.seat) => (8, 5),
+                        (false, _) => (10, if suit == Suit::Hearts { 5 } else { 4 }),
+                    }
+                }
+                (BiddingSuit::Suit(_), _) => (if jump { 16 } else { 12 }, 4),
+            };
+            points = points.max(shown);
+            if let BiddingSuit::Suit(suit) = bid.suit {
+                let idx = SUITS.iter().position(|other| *other == suit).unwrap();
+                // A suit bid again shows an extra card.
+                lengths[idx] = if lengths[idx] > 0 {
+                    (lengths[idx] + 1).max(5)
+                } else {
+                    length
+                };
+            }
+            partner_bids += 1;
+            last_bid = Some((seat, *bid));
+        }
+        (points, lengths)
+    }
+
+    /// Returns a call in later rounds: bid game with enough points between the two hands,
+    /// invite it with nearly enough, and otherwise settle in a part score.
+    fn place(&self) -> AuctionBid {
+        let (points, lengths) = self.partner_shows();
+        let mut combined = self.hcp + points;
+        let partner = self.seat.partner();
+        if self.opening().is_some_and(|(seat, bid)| {
+            seat == partner && bid == ContractBid::new(2, Suit::Clubs.into()).unwrap()
+        }) {
+            combined = combined.max(25);
+        }
+
+        // The best fit: a major before a minor, and the longer before the shorter. A suit
+        // of six cards or more which has been bid needs little from partner.
+        let mine = self.calls(self.seat);
+        let fit = SUITS
+            .into_iter()
+            .zip(lengths)
+            .map(|(suit, length)| {
+                let bid = mine
+                    .iter()
+                    .any(|call| matches!(call, AuctionBid::Bid(bid) if bid.suit == suit.into()));
+                if bid && self.len(suit) >= 6 {
+                    (suit, length.max(2))
+                } else {
+                    (suit, length)
+                }
+            })
+            .filter(|(suit, length)| self.len(*suit) + length >= 8)
+            .max_by_key(|(suit, length)| (is_major(*suit), self.len(*suit) + length))
+            .map(|(suit, _)| suit);
+        let strain = match fit {
+            Some(suit) if is_major(suit) => BiddingSuit::Suit(suit),
+            Some(suit) if !self.balanced() && combined < 25 => BiddingSuit::Suit(suit),
+            _ => BiddingSuit::NoTrumps,
+        };
+
+        let level = match combined {
+            25.. => game_level(strain),
+            23..=24 => game_level(strain) - 1,
+            _ => 0,
+        };
+
+        let current = self.current();
+        let ours = self
+            .auction
+            .contract()
+            .is_some_and(|contract| contract.declarer.side() == self.seat.side());
+        if level == 0 {
+            // Correct to the fit from a contract in another strain, at the cheapest level,
+            // and keep the auction open below a one-level suit without one.
+            return match (current, fit) {
+                (Some(current), Some(suit)) if ours && current.suit != BiddingSuit::Suit(suit) => {
+                    match self.cheapest(suit.into()) {
+                        Some(level) if level <= 3 => suit_bid(level, suit),
+                        _ => AuctionBid::Pass,
+                    }
+                }
+                (Some(current), None) if ours && u8::from(current.level) == 1 && self.hcp >= 6 => {
+                    self.cheapest_bid(BiddingSuit::NoTrumps, 0)
+                }
+                _ => AuctionBid::Pass,
+            };
+        }
+
+        let target = ContractBid::new(level, strain).unwrap();
+        match current {
+            Some(current) if ours && u8::from(current.level) >= game_level(current.suit) => {
+                AuctionBid::Pass
+            }
+            Some(current) if current >= target => AuctionBid::Pass,
+            _ => AuctionBid::Bid(target),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::deck::Deck;
+
+    fn hand(hand: &str) -> Hand {
+        hand.parse().unwrap()
+    }
+
+    /// Returns an auction from the given dealer with the given calls.
+    fn auction(dealer: Seat, calls: &str) -> Auction {
+        let mut auction = Auction::new(dealer);
+        for call in calls.split_whitespace() {
+            auction.enter_bid(call.parse().unwrap()).unwrap();
+        }
+        auction
+    }
+
+    fn call(hand_str: &str, calls: &str) -> String {
+        StandardAmerican
+            .bid(&hand(hand_str), &auction(Seat::North, calls))
+            .to_string()
+    }
+
+    #[test]
+    fn openings() {
+        assert_eq!("1NT", call("AQ52.K84.KJ3.Q92", ""));
+        assert_eq!("1♠", call("AKJ52.K84.Q3.972", ""));
+        assert_eq!("1♥", call("AKJ5.K8642.Q3.97", ""));
+        assert_eq!("1♦", call("AK52.K8.Q963.J72", ""));
+        assert_eq!("1♣", call("AK5.K84.Q96.J732", "Pass"));
+        assert_eq!("2♣", call("AKQ5.AK4.AQ3.K72", ""));
+        assert_eq!("2NT", call("AKQ5.AJ4.KQ3.Q72", ""));
+        assert_eq!("2♥", call("52.KQJ952.73.842", ""));
+        assert_eq!("3♠", call("KQJ9532.2.73.842", "Pass"));
+        assert_eq!("Pass", call("KQJ9532.2.73.842", "Pass Pass Pass"));
+        assert_eq!("Pass", call("Q52.K84.J93.9732", ""));
+    }
+
+    #[test]
+    fn responses() {
+        assert_eq!("2♠", call("K73.Q842.983.J72", "1S Pass"));
+        assert_eq!("3♠", call("K73.Q842.A83.J72", "1S Pass"));
+        assert_eq!("1♥", call("K73.Q842.983.J72", "1D Pass"));
+        assert_eq!("2♣", call("K7.Q84.A83.KJ972", "1S Pass"));
+        assert_eq!("1NT", call("K7.Q84.J83.Q9732", "1S Pass"));
+        assert_eq!("Pass", call("K73.842.983.9872", "1S Pass"));
+        assert_eq!("3NT", call("K73.Q84.A83.KJ72", "1NT Pass"));
+        assert_eq!("2♥", call("73.Q8642.983.J72", "1NT Pass"));
+        assert_eq!("2♦", call("73.842.9832.J872", "2C Pass"));
+    }
+
+    #[test]
+    fn rebids() {
+        assert_eq!("Pass", call("AKJ52.K84.Q3.972", "1S Pass 2S Pass"));
+        assert_eq!("4♠", call("AKJ52.K84.AQ3.72", "1S Pass 3S Pass"));
+        assert_eq!("2♥", call("AKJ5.K842.Q3.972", "1C Pass 1H Pass"));
+        assert_eq!("1NT", call("A52.K84.Q3.KJ972", "1C Pass 1H Pass"));
+        assert_eq!("4♠", call("A52.K84.A3.KQ972", "1NT Pass 3S Pass"));
+        assert_eq!("3NT", call("A5.K84.AQJ3.K972", "1NT Pass 3S Pass"));
+    }
+
+    #[test]
+    fn competition() {
+        assert_eq!("1♠", call("AQJ52.K84.93.972", "1H"));
+        assert_eq!("Dbl", call("AQ52.K.Q932.J972", "1H"));
+        assert_eq!("1NT", call("AQ5.KJ4.A93.Q972", "1H"));
+        assert_eq!("Pass", call("Q52.K84.932.J972", "1H"));
+        assert_eq!("1♠", call("K852.Q84.932.J72", "1H Dbl Pass"));
+        assert_eq!("2♠", call("K852.Q84.932.J72", "1H 1S Pass"));
+    }
+
+    #[test]
+    fn complete_auctions() {
+        for seed in 0..50 {
+            let deal = Deck::from_seed(seed).deal();
+            let mut auction = Auction::new(Seat::from_repr(seed as usize % 4).unwrap());
+            while !auction.closed() {
+                let seat = auction.turn();
+                let call = StandardAmerican.bid(&deal[seat], &auction);
+                auction.enter_bid(call).unwrap();
+                assert!(auction.sequence().len() < 40);
+            }
+        }
+    }
+}
@@ -0,0 +1,140 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{
+    auction::{Auction, Contract, Seat, Side},
+    error::ParseError,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Vulnerability {
+    None,
+    NorthSouth,
+    EastWest,
+    Both,
+}
+
+impl Vulnerability {
+    pub fn is_vulnerable(&self, side: Side) -> bool {
+        match self {
+            Self::None => false,
+            Self::NorthSouth => side == Side::NorthSouth,
+            Self::EastWest => side == Side::EastWest,
+            Self::Both => true,
+        }
+    }
+}
+
+impl FromStr for Vulnerability {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "none" | "love" | "-" => Ok(Self::None),
+            "ns" | "n-s" => Ok(Self::NorthSouth),
+            "ew" | "e-w" => Ok(Self::EastWest),
+            "all" | "both" => Ok(Self::Both),
+            _ => Err(ParseError::VulnerabilityNotValid),
+        }
+    }
+}
+
+impl Display for Vulnerability {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::None => "None",
+            Self::NorthSouth => "N-S",
+            Self::EastWest => "E-W",
+            Self::Both => "All",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Board {
+    pub number: u32,
+    pub dealer: Seat,
+    pub vulnerability: Vulnerability,
+}
+
+impl Board {
+    /// Returns the board with the given number, with the dealer and vulnerability given
+    /// by the standard 16-board duplicate rotation, which repeats from board 17.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `number` is 0, as boards are numbered from 1.
+    pub fn new(number: u32) -> Self {
+        assert!(number > 0, "boards are numbered from 1");
+        use Vulnerability as V;
+        #[rustfmt::skip]
+        const ROTATION: [Vulnerability; 16] = [
+            V::None, V::NorthSouth, V::EastWest, V::Both,
+            V::NorthSouth, V::EastWest, V::Both, V::None,
+            V::EastWest, V::Both, V::None, V::NorthSouth,
+            V::Both, V::None, V::NorthSouth, V::EastWest,
+        ];
+
+        let idx = (number as usize + 15) % 16;
+        Self {
+            number,
+            dealer: Seat::from_repr(idx % 4).unwrap(),
+            vulnerability: ROTATION[idx],
+        }
+    }
+
+    /// Returns an empty auction for the board, with the board's dealer to call first.
+    pub fn auction(&self) -> Auction {
+        Auction::new(self.dealer)
+    }
+
+    pub fn is_vulnerable(&self, side: Side) -> bool {
+        self.vulnerability.is_vulnerable(side)
+    }
+
+    /// Returns the duplicate score for a contract played on this board, from the
+    /// declaring side's point of view.
+    pub fn score(&self, contract: &Contract, tricks: u8) -> i32 {
+        contract.score(self.is_vulnerable(contract.declarer.side()), tricks)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rotation() {
+        let board = Board::new(7);
+        assert_eq!(Seat::South, board.dealer);
+        assert_eq!(Vulnerability::Both, board.vulnerability);
+
+        assert_eq!(Seat::North, Board::new(1).dealer);
+        assert_eq!(Vulnerability::None, Board::new(1).vulnerability);
+        assert_eq!(Seat::West, Board::new(16).dealer);
+        assert_eq!(Vulnerability::EastWest, Board::new(16).vulnerability);
+        assert_eq!(Seat::East, Board::new(10).dealer);
+        assert_eq!(Vulnerability::Both, Board::new(10).vulnerability);
+        assert_eq!(Vulnerability::NorthSouth, Board::new(12).vulnerability);
+
+        assert_eq!(Board::new(3).dealer, Board::new(19).dealer);
+        assert_eq!(Board::new(3).vulnerability, Board::new(19).vulnerability);
+        assert_eq!(19, Board::new(19).number);
+    }
+
+    #[test]
+    #[should_panic]
+    fn board_numbered_zero() {
+        Board::new(0);
+    }
+
+    #[test]
+    fn vulnerable_scoring() {
+        let board = Board::new(2);
+        assert!(board.is_vulnerable(Side::NorthSouth));
+        assert!(!board.is_vulnerable(Side::EastWest));
+
+        assert_eq!(620, board.score(&"N:4S".parse().unwrap(), 10));
+        assert_eq!(420, board.score(&"E:4S".parse().unwrap(), 10));
+        assert_eq!(Seat::East, *board.auction().dealer());
+    }
+}
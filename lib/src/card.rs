@@ -3,11 +3,11 @@ use std::{
     str::FromStr,
 };
 
-use strum::EnumIter;
+use strum::{EnumIter, FromRepr};
 
 use crate::error::ParseError;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, EnumIter)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, EnumIter, FromRepr)]
 pub enum Suit {
     Spades = 3,
     Hearts = 2,
@@ -65,7 +65,7 @@ impl Display for Suit {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, EnumIter)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, EnumIter, FromRepr)]
 pub enum Rank {
     Two = 2,
     Three,
@@ -0,0 +1,257 @@
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, Not, Sub, SubAssign};
+
+use crate::card::{Card, Rank, Suit};
+
+/// The bits of a suit's lane in which cards can be held: ranks 2 to ace.
+const LANE: u64 = 0x7ffc;
+
+/// A set of cards, as a bitboard. Each suit has its own 16-bit lane, from clubs in the
+/// lowest to spades in the highest, and each card sits at the bit of its rank within the
+/// lane, so that a higher bit is always a higher card.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct CardSet(u64);
+
+impl CardSet {
+    pub const EMPTY: Self = Self(0);
+    pub const ALL: Self = Self(LANE | LANE << 16 | LANE << 32 | LANE << 48);
+
+    fn bit(card: Card) -> u64 {
+        1 << (card.suit as usize * 16 + card.rank as usize)
+    }
+
+    /// Returns the set with the given bitboard, ignoring any bits which aren't cards.
+    pub fn from_bits(bits: u64) -> Self {
+        Self(bits & Self::ALL.0)
+    }
+
+    pub fn bits(&self) -> u64 {
+        self.0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn contains(&self, card: Card) -> bool {
+        self.0 & Self::bit(card) != 0
+    }
+
+    /// Adds the given card to the set, returning whether it was newly added.
+    pub fn insert(&mut self, card: Card) -> bool {
+        let held = self.contains(card);
+        self.0 |= Self::bit(card);
+        !held
+    }
+
+    /// Removes the given card from the set, returning whether it was held.
+    pub fn remove(&mut self, card: Card) -> bool {
+        let held = self.contains(card);
+        self.0 &= !Self::bit(card);
+        held
+    }
+
+    /// Returns the cards of the set in the given suit.
+    pub fn suit(&self, suit: Suit) -> Self {
+        Self(self.0 & LANE << (suit as usize * 16))
+    }
+
+    pub fn suit_len(&self, suit: Suit) -> usize {
+        self.suit(suit).len()
+    }
+
+    /// Returns the highest card of the set, if any.
+    pub fn highest(&self) -> Option<Card> {
+        self.iter().next()
+    }
+
+    /// Returns the lowest card of the set, if any.
+    pub fn lowest(&self) -> Option<Card> {
+        self.iter().next_back()
+    }
+
+    /// Iterates over the cards of the set from the highest (the ace of spades, if held) to
+    /// the lowest.
+    pub fn iter(&self) -> Iter {
+        Iter(self.0)
+    }
+}
+
+/// An iterator over the cards of a [`CardSet`], from high to low.
+#[derive(Debug, Clone)]
+pub struct Iter(u64);
+
+fn card_at(bit: u32) -> Card {
+    Card {
+        suit: Suit::from_repr(bit as usize / 16).unwrap(),
+        rank: Rank::from_repr(bit as usize % 16).unwrap(),
+    }
+}
+
+impl Iterator for Iter {
+    type Item = Card;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.0 == 0 {
+            return None;
+        }
+        let bit = 63 - self.0.leading_zeros();
+        self.0 &= !(1 << bit);
+        Some(card_at(bit))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.count_ones() as usize;
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for Iter {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.0 == 0 {
+            return None;
+        }
+        let bit = self.0.trailing_zeros();
+        self.0 &= self.0 - 1;
+        Some(card_at(bit))
+    }
+}
+
+impl ExactSizeIterator for Iter {}
+
+impl IntoIterator for CardSet {
+    type Item = Card;
+    type IntoIter = Iter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl FromIterator<Card> for CardSet {
+    fn from_iter<T: IntoIterator<Item = Card>>(iter: T) -> Self {
+        Self(
+            iter.into_iter()
+                .fold(0, |bits, card| bits | Self::bit(card)),
+        )
+    }
+}
+
+impl From<Vec<Card>> for CardSet {
+    fn from(value: Vec<Card>) -> Self {
+        value.into_iter().collect()
+    }
+}
+
+impl From<CardSet> for Vec<Card> {
+    fn from(value: CardSet) -> Self {
+        value.iter().collect()
+    }
+}
+
+impl BitOr for CardSet {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self::Output {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl BitOrAssign for CardSet {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl BitAnd for CardSet {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self::Output {
+        Self(self.0 & rhs.0)
+    }
+}
+
+impl BitAndAssign for CardSet {
+    fn bitand_assign(&mut self, rhs: Self) {
+        self.0 &= rhs.0;
+    }
+}
+
+impl Sub for CardSet {
+    type Output = Self;
+
+    /// Returns the cards of the set which aren't in `rhs`.
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self(self.0 & !rhs.0)
+    }
+}
+
+impl SubAssign for CardSet {
+    fn sub_assign(&mut self, rhs: Self) {
+        self.0 &= !rhs.0;
+    }
+}
+
+impl Not for CardSet {
+    type Output = Self;
+
+    /// Returns every card not in the set.
+    fn not(self) -> Self::Output {
+        Self(!self.0 & Self::ALL.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn cards(cards: &[&str]) -> CardSet {
+        cards
+            .iter()
+            .map(|card| card.parse::<Card>().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn membership() {
+        let mut set = cards(&["AS", "2C", "TH"]);
+        assert_eq!(3, set.len());
+        assert!(set.contains("TH".parse().unwrap()));
+        assert!(!set.contains("TD".parse().unwrap()));
+
+        assert!(set.insert("KD".parse().unwrap()));
+        assert!(!set.insert("KD".parse().unwrap()));
+        assert!(set.remove("AS".parse().unwrap()));
+        assert!(!set.remove("AS".parse().unwrap()));
+        assert_eq!(cards(&["2C", "TH", "KD"]), set);
+
+        assert_eq!(52, CardSet::ALL.len());
+        assert!(CardSet::EMPTY.is_empty());
+        assert_eq!(CardSet::ALL, !CardSet::EMPTY);
+    }
+
+    #[test]
+    fn set_operations() {
+        let lhs = cards(&["AS", "KS", "2C"]);
+        let rhs = cards(&["KS", "2C", "3D"]);
+        assert_eq!(cards(&["AS", "KS", "2C", "3D"]), lhs | rhs);
+        assert_eq!(cards(&["KS", "2C"]), lhs & rhs);
+        assert_eq!(cards(&["AS"]), lhs - rhs);
+        assert_eq!(49, (!lhs).len());
+        assert_eq!(cards(&["AS", "KS"]), lhs.suit(Suit::Spades));
+        assert_eq!(0, lhs.suit_len(Suit::Hearts));
+    }
+
+    #[test]
+    fn iteration() {
+        let set = cards(&["2C", "QH", "AS", "TH", "3D"]);
+        let order: Vec<String> = set.iter().map(|card| card.to_string()).collect();
+        assert_eq!(vec!["♠A", "♥Q", "♥10", "♦3", "♣2"], order);
+        assert_eq!(Some("AS".parse().unwrap()), set.highest());
+        assert_eq!(Some("2C".parse().unwrap()), set.lowest());
+        assert_eq!(set, CardSet::from(Vec::from(set)));
+    }
+}
@@ -0,0 +1,1040 @@
+//! Double-dummy analysis: the number of tricks each side takes when every player can see
+//! all four hands and plays perfectly.
+
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    hash::{BuildHasherDefault, Hasher},
+};
+
+use crate::{
+    auction::{BiddingSuit, Seat, Side},
+    card::{Card, Suit},
+    cardset::CardSet,
+    hand::Hand,
+};
+
+/// The bits of a suit's lane in which cards are held: ranks 2 to 14.
+const LANE: u64 = 0x7ffc;
+
+fn lane(cards: u64, suit: usize) -> u64 {
+    (cards >> (suit * 16)) & LANE
+}
+
+fn suit_of(card: u64) -> usize {
+    card.trailing_zeros() as usize / 16
+}
+
+/// Returns the highest of a non-empty set of cards.
+fn top(cards: u64) -> u64 {
+    1 << (63 - cards.leading_zeros())
+}
+
+/// Returns the given card and every card above it in its suit.
+fn and_above(card: u64) -> u64 {
+    !(card - 1) & (LANE << (suit_of(card) * 16))
+}
+
+/// `GATHER[mask][bits]` holds the bits of `bits` at the set bits of `mask`, packed into
+/// the low bits, for masks and bits of seven bits.
+static GATHER: [[u8; 128]; 128] = {
+    let mut table = [[0; 128]; 128];
+    let mut mask = 0;
+    while mask < 128 {
+        let mut bits = 0;
+        while bits < 128 {
+            let (mut bit, mut packed, mut count) = (0, 0, 0);
+            while bit < 7 {
+                if mask & (1 << bit) != 0 {
+                    packed |= ((bits >> bit) & 1) << count;
+                    count += 1;
+                }
+                bit += 1;
+            }
+            table[mask][bits] = packed as u8;
+            bits += 1;
+        }
+        mask += 1;
+    }
+    table
+};
+
+/// Returns the bits of a suit's `bits` at the set bits of `mask`, one above the other
+/// in every other bit, with the bit for the lowest rank in the lowest bit. Both hold a
+/// suit's ranks in their lowest 13 bits.
+fn gather(bits: u64, mask: u64) -> u64 {
+    let (low, high) = ((mask & 0x7f) as usize, (mask >> 7) as usize);
+    let packed = GATHER[low][bits as usize & 0x7f] as u64
+        | (GATHER[high][bits as usize >> 7] as u64) << low.count_ones();
+    // Spread the packed bits apart.
+    let spread = (packed | packed << 8) & 0x00ff_00ff;
+    let spread = (spread | spread << 4) & 0x0f0f_0f0f;
+    let spread = (spread | spread << 2) & 0x3333_3333;
+    (spread | spread << 1) & 0x5555_5555
+}
+
+/// Returns a player's length in a suit, given the lengths of [`Solver::lengths`].
+fn length(lengths: u64, seat: usize, suit: usize) -> u8 {
+    (lengths >> (48 - 16 * seat + 4 * suit)) as u8 & 0xf
+}
+
+fn is_north_south(seat: usize) -> bool {
+    seat.is_multiple_of(2)
+}
+
+/// The cards whose ranks a result depends on. Any other card could be swapped for a lower
+/// card of the same suit without changing the result, so in each suit the relevant cards
+/// are always the top ones.
+type Relevant = u64;
+
+/// Bounds on the number of tricks North-South can take, which hold for every position with
+/// the same suit lengths in which the same players hold the relevant top cards.
+#[derive(Debug, Clone, Copy)]
+struct Entry {
+    /// The owners of the relevant cards, in the layout of [`Solver::owners`].
+    pattern: u128,
+    mask: u128,
+    lower: u8,
+    upper: u8,
+    /// The lead which last decided a search from the position.
+    best: u64,
+}
+
+/// A fast hasher for the integer keys of the transposition table.
+#[derive(Default)]
+struct KeyHasher(u64);
+
+impl Hasher for KeyHasher {
+    fn finish(&self) -> u64 {
+        // The multiplication leaves the low bits, which pick the bucket, poorly mixed.
+        self.0.rotate_left(26)
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        bytes.iter().for_each(|byte| self.write_u64(*byte as u64));
+    }
+
+    fn write_u64(&mut self, n: u64) {
+        self.0 = (self.0.rotate_left(5) ^ n).wrapping_mul(0x517c_c1b7_2722_0a95);
+    }
+
+    fn write_usize(&mut self, n: usize) {
+        self.write_u64(n as u64);
+    }
+}
+
+/// The state of a search. Hands are bitboards indexed by seat, in the layout of
+/// [`CardSet`](crate::cardset::CardSet): each suit in its own 16-bit lane and each card at the bit of its rank.
+struct Solver {
+    hands: [u64; 4],
+    trumps: Option<usize>,
+    table: HashMap<(u64, usize), Vec<Entry>, BuildHasherDefault<KeyHasher>>,
+}
+
+impl Solver {
+    fn new(hands: &[Hand; 4], strain: BiddingSuit) -> Self {
+        assert!(
+            hands.iter().all(|hand| hand.len() == hands[0].len()),
+            "hands must all hold the same number of cards"
+        );
+        Self::unchecked(hands, strain)
+    }
+
+    /// Like [`Solver::new`], but for a position part way through a trick, in which the
+    /// players who have played to it hold a card fewer.
+    fn unchecked(hands: &[Hand; 4], strain: BiddingSuit) -> Self {
+        Self {
+            hands: std::array::from_fn(|seat| hands[seat].cards().bits()),
+            trumps: strain.trumps().map(|suit| suit as usize),
+            table: HashMap::default(),
+        }
+    }
+
+    fn remaining(&self) -> u8 {
+        self.hands[0].count_ones() as u8
+    }
+
+    fn live(&self) -> u64 {
+        self.hands.iter().fold(0, |cards, hand| cards | hand)
+    }
+
+    /// Returns the maximum number of tricks North-South can take from the current
+    /// position, with `leader` on lead to an empty trick, trying the numbers either side
+    /// of `guess` first.
+    fn north_south_tricks(&mut self, leader: usize, guess: Option<u8>) -> u8 {
+        let remaining = self.remaining();
+        self.highest(remaining, guess, |solver, target| {
+            solver.search(leader, target).0
+        })
+    }
+
+    /// Returns the maximum number of tricks North-South can take after each card the seat
+    /// on turn may play, counting the current trick, with `position` cards already played
+    /// to a trick led by `leader`.
+    fn north_south_tricks_after(
+        &mut self,
+        leader: usize,
+        position: usize,
+        trick: &mut [u64; 4],
+    ) -> Vec<(u64, u8)> {
+        let seat = (leader + position) % 4;
+        let (hand, remaining) = (self.hands[seat], self.hands[seat].count_ones() as u8);
+        let mut cards = match (position > 0).then(|| suit_of(trick[0])) {
+            Some(led) if lane(hand, led) != 0 => hand & (LANE << (led * 16)),
+            _ => hand,
+        };
+
+        let mut tricks: Vec<(u64, u8)> = vec![];
+        while cards != 0 {
+            let card = cards & cards.wrapping_neg();
+            cards &= !card;
+            self.hands[seat] &= !card;
+            trick[position] = card;
+
+            // Cards tend to take as many tricks as the card tried before them, or one
+            // more or fewer.
+            let guess = tricks.last().map(|(_, tricks)| *tricks);
+            let north_south = self.highest(remaining, guess, |solver, target| {
+                if position == 3 {
+                    let winner = solver.winner(leader, trick);
+                    let won = is_north_south(winner) as u8;
+                    solver.search(winner, target - won.min(target)).0
+                } else {
+                    solver.play(leader, position + 1, trick, target, 0).0
+                }
+            });
+
+            self.hands[seat] |= card;
+            tricks.push((card, north_south));
+        }
+        tricks
+    }
+
+    /// Returns the highest target up to `upper` which `test` passes, given that it passes
+    /// for every lower target. The targets either side of `guess` are tried before
+    /// bisecting.
+    fn highest(
+        &mut self,
+        upper: u8,
+        guess: Option<u8>,
+        mut test: impl FnMut(&mut Self, u8) -> bool,
+    ) -> u8 {
+        let (mut lower, mut upper) = (0, upper);
+        let mut guesses: u8 = if guess.is_some() { 2 } else { 0 };
+        let mut guess = guess.unwrap_or(0);
+        while lower < upper {
+            let target = match guesses > 0 && guess > lower && guess <= upper {
+                true => guess,
+                false => (lower + upper).div_ceil(2),
+            };
+            guesses = guesses.saturating_sub(1);
+            if test(self, target) {
+                lower = target;
+                guess = target + 1;
+            } else {
+                upper = target - 1;
+                guess = target - 1;
+            }
+        }
+        lower
+    }
+
+    /// The number of cards each player holds in each suit, four bits apiece.
+    fn lengths(&self) -> u64 {
+        self.hands.iter().fold(0, |lengths, hand| {
+            // Count the cards of every suit at once, then gather the counts into a nibble each.
+            let counts = hand - ((hand >> 1) & 0x5555_5555_5555_5555);
+            let counts = (counts & 0x3333_3333_3333_3333) + ((counts >> 2) & 0x3333_3333_3333_3333);
+            let counts = (counts + (counts >> 4)) & 0x0f0f_0f0f_0f0f_0f0f;
+            let counts = (counts + (counts >> 8)) & 0x00ff_00ff_00ff_00ff;
+            (lengths << 16) | (counts | counts >> 12 | counts >> 24 | counts >> 36) & 0xffff
+        })
+    }
+
+    /// The owners of the remaining cards of each suit from the top down, two bits per card
+    /// in a 32-bit lane per suit with the top card in the highest bits.
+    fn owners(&self, lengths: u64) -> u128 {
+        // The two bits of each seat's index, as sets of cards.
+        let low = self.hands[1] | self.hands[3];
+        let high = self.hands[2] | self.hands[3];
+        let live = self.live();
+        let mut owners = 0;
+        for suit in 0..4 {
+            let live = lane(live, suit) >> 2;
+            if live == 0 {
+                continue;
+            }
+            let seats =
+                gather(lane(low, suit) >> 2, live) | gather(lane(high, suit) >> 2, live) << 1;
+            let count: usize = (0..4)
+                .map(|seat| length(lengths, seat, suit) as usize)
+                .sum();
+            owners |= (seats as u128) << (suit * 32 + 32 - 2 * count);
+        }
+        owners
+    }
+
+    /// Returns the mask of [`Solver::owners`] which covers the relevant cards.
+    fn mask(&self, relevant: Relevant) -> u128 {
+        let live = self.live();
+        let mut mask = 0;
+        for suit in 0..4 {
+            let count = lane(live & relevant, suit).count_ones();
+            if count > 0 {
+                mask |= ((1u128 << (2 * count)) - 1) << (suit as u32 * 32 + 32 - 2 * count);
+            }
+        }
+        mask
+    }
+
+    /// Returns the cards covered by a mask of [`Solver::owners`].
+    fn relevant(&self, mask: u128) -> Relevant {
+        let live = self.live();
+        let mut relevant = 0;
+        for suit in 0..4 {
+            let count = ((mask >> (suit * 32)) as u32).count_ones() / 2;
+            let mut cards = lane(live, suit);
+            if count > 0 {
+                for _ in 1..count {
+                    cards &= !top(cards);
+                }
+                relevant |= and_above(top(cards) << (suit * 16));
+            }
+        }
+        relevant
+    }
+
+    /// Returns whether North-South can take at least `target` of the remaining tricks,
+    /// with `leader` on lead to an empty trick, and the cards the result depends on.
+    fn search(&mut self, leader: usize, target: u8) -> (bool, Relevant) {
+        let remaining = self.remaining();
+        if target == 0 {
+            return (true, 0);
+        }
+        if target > remaining {
+            return (false, 0);
+        }
+        if remaining == 1 {
+            let trick = std::array::from_fn(|position| self.hands[(leader + position) % 4]);
+            let winner = self.winner(leader, &trick);
+            let relevant = self.won_by_rank(&trick, trick[(winner + 4 - leader) % 4]);
+            return (is_north_south(winner), relevant);
+        }
+        let lengths = self.lengths();
+        if let Some((result, _, relevant)) = self.cutoff(leader, target, lengths) {
+            return (result, relevant);
+        }
+
+        let key = (lengths, leader);
+        let owners = self.owners(lengths);
+        let mut best = 0;
+        if let Some(entries) = self.table.get_mut(&key) {
+            // Positions which recur tend to recur often, so the entries last used are kept
+            // at the back and tried first.
+            for idx in (0..entries.len()).rev() {
+                let entry = entries[idx];
+                if owners & entry.mask != entry.pattern {
+                    // A lead which worked in a similar position is worth trying first.
+                    if best == 0 && entry.best & self.hands[leader] != 0 {
+                        best = entry.best;
+                    }
+                    continue;
+                }
+                if entry.lower >= target || entry.upper < target {
+                    entries[idx..].rotate_left(1);
+                    return (entry.lower >= target, self.relevant(entry.mask));
+                }
+                if entry.best & self.hands[leader] != 0 {
+                    best = entry.best;
+                }
+            }
+        }
+
+        let (result, best, relevant) = self.play(leader, 0, &mut [0; 4], target, best);
+
+        let mask = self.mask(relevant);
+        let pattern = owners & mask;
+        let entries = self.table.entry(key).or_default();
+        let idx = match entries
+            .iter()
+            .rposition(|entry| entry.mask == mask && entry.pattern == pattern)
+        {
+            Some(idx) => idx,
+            None => {
+                entries.push(Entry {
+                    pattern,
+                    mask,
+                    lower: 0,
+                    upper: remaining,
+                    best: 0,
+                });
+                entries.len() - 1
+            }
+        };
+        let entry = &mut entries[idx];
+        if best != 0 {
+            entry.best = best;
+        }
+        if result {
+            entry.lower = entry.lower.max(target);
+        } else {
+            entry.upper = entry.upper.min(target - 1);
+        }
+        (result, relevant)
+    }
+
+    /// Returns the result of a search without playing any cards, if it can be told from the
+    /// tricks the leader's side can cash straight away or from who holds the top trump,
+    /// and the cards the result depends on.
+    fn cutoff(&self, leader: usize, target: u8, lengths: u64) -> Option<(bool, u64, Relevant)> {
+        let remaining = self.remaining();
+        let north_south = is_north_south(leader);
+        let needed = match north_south {
+            true => target,
+            false => remaining + 1 - target,
+        };
+        if let Some((best, relevant)) = self.quick_tricks(leader, needed, lengths) {
+            return Some((north_south, best, relevant));
+        }
+
+        // The top trump takes a trick sooner or later.
+        let (north_south, relevant) = self.later_trick()?;
+        match (north_south, target) {
+            (true, 1) => Some((true, 0, relevant)),
+            (false, target) if target == remaining => Some((false, 0, relevant)),
+            _ => None,
+        }
+    }
+
+    /// Returns whether the leader's side can take `needed` tricks straight away, and if so
+    /// the card to lead and the cards that depends on. The leader cashes its top cards, and
+    /// may then lead to partner's top card of a suit, after which partner cashes theirs.
+    /// In a trump contract in which the opponents hold trumps, only one suit is cashed,
+    /// and only as many times as each opponent with trumps can follow.
+    fn quick_tricks(&self, leader: usize, needed: u8, lengths: u64) -> Option<(u64, Relevant)> {
+        let live = self.live();
+        let (hand, partner) = (self.hands[leader], self.hands[(leader + 2) % 4]);
+        let opponents = [self.hands[(leader + 1) % 4], self.hands[(leader + 3) % 4]];
+        let ruffers = match self.trumps {
+            Some(trumps) => opponents.map(|hand| lane(hand, trumps) != 0),
+            None => [false; 2],
+        };
+
+        // For each suit, the tricks the leader cashes, the further tricks partner cashes
+        // once on lead, whether the leader can put partner on lead, whether partner follows
+        // to every trick the leader cashes, the card to lead first and the cards the tricks
+        // depend on.
+        let mut suits = [(0, 0, false, false, 0, 0); 4];
+        for (suit, cashes) in suits.iter_mut().enumerate() {
+            let (held, partner_held) = (lane(hand, suit), lane(partner, suit));
+            // The number of rounds the opponents can follow to without ruffing.
+            let mut rounds = 13;
+            for (opponent, ruffs) in [leader + 1, leader + 3].into_iter().zip(ruffers) {
+                if ruffs && Some(suit) != self.trumps {
+                    rounds = rounds.min(length(lengths, opponent % 4, suit));
+                }
+            }
+
+            let (mut cards, mut tricks, mut lowest) = (lane(live, suit), 0, 0);
+            while cards != 0 && tricks < rounds && held & top(cards) != 0 {
+                (tricks, lowest) = (tricks + 1, top(cards));
+                cards &= !lowest;
+            }
+            // Partner follows to the leader's tricks with their lowest cards, so keeps their
+            // top cards unless they hold no others.
+            let partner_length = length(lengths, (leader + 2) % 4, suit);
+            let (mut more, mut kept) = (0, partner_length.saturating_sub(tricks));
+            while cards != 0 && tricks + more < rounds && kept > 0 && partner_held & top(cards) != 0
+            {
+                (more, kept, lowest) = (more + 1, kept - 1, top(cards));
+                cards &= !lowest;
+            }
+            let entry = more > 0 && length(lengths, leader, suit) > tricks;
+
+            // Only the lengths matter if nobody else can follow suit.
+            let depends = match lane(live, suit) == held || lane(live, suit) == partner_held {
+                true => 0,
+                false if lowest == 0 => 0,
+                false => and_above(lowest << (suit * 16)),
+            };
+            let first = match held {
+                0 => 0,
+                held => top(held) << (suit * 16),
+            };
+            *cashes = (
+                tricks,
+                more,
+                entry,
+                partner_length >= tricks,
+                first,
+                depends,
+            );
+        }
+
+        if ruffers.contains(&true) {
+            return suits
+                .iter()
+                .map(|(tricks, more, entry, _, first, depends)| match entry {
+                    true => (tricks + more, *first, *depends),
+                    false => (*tricks, *first, *depends),
+                })
+                .find(|(tricks, _, _)| *tricks >= needed)
+                .map(|(_, first, depends)| (first, depends));
+        }
+
+        // The leader cashes the suits with the most tricks first.
+        let mut order = [0, 1, 2, 3];
+        order.sort_by_key(|suit| std::cmp::Reverse(suits[*suit].0));
+        let (mut tricks, mut first, mut relevant) = (0, 0, 0);
+        for (cashes, _, _, _, card, depends) in order.map(|suit| suits[suit]) {
+            if tricks >= needed || cashes == 0 {
+                break;
+            }
+            tricks += cashes;
+            relevant |= depends;
+            if first == 0 {
+                first = card;
+            }
+        }
+        if tricks >= needed {
+            return Some((first, relevant));
+        }
+
+        // Otherwise the leader crosses to partner through the suit which gives partner the
+        // most tricks, having first cashed the suits in which partner can't be made to
+        // discard.
+        let entry = (0..4)
+            .filter(|suit| suits[*suit].2)
+            .max_by_key(|suit| suits[*suit].1)?;
+        let (mut tricks, mut first, mut relevant) =
+            (suits[entry].0 + suits[entry].1, 0, suits[entry].5);
+        for suit in order {
+            let (cashes, more, _, follows, card, depends) = suits[suit];
+            if tricks >= needed {
+                break;
+            }
+            if suit != entry && follows {
+                tricks += cashes + more;
+                relevant |= depends;
+                if first == 0 && cashes > 0 {
+                    first = card;
+                }
+            }
+        }
+        (tricks >= needed).then_some((if first == 0 { suits[entry].4 } else { first }, relevant))
+    }
+
+    /// Returns whether North-South, rather than East-West, hold the top trump, which is
+    /// bound to take a trick, and the cards that depends on.
+    fn later_trick(&self) -> Option<(bool, Relevant)> {
+        let suit = self.trumps?;
+        let trumps = lane(self.live(), suit);
+        if trumps == 0 {
+            return None;
+        }
+        let card = top(trumps) << (suit * 16);
+        let seat = self.hands.iter().position(|hand| hand & card != 0)?;
+        Some((is_north_south(seat), and_above(card)))
+    }
+
+    /// Searches the remaining plays to a trick, with `position` cards already played,
+    /// trying `first` before any other card. Returns the result, the card which decided
+    /// it, and the cards it depends on.
+    fn play(
+        &mut self,
+        leader: usize,
+        position: usize,
+        trick: &mut [u64; 4],
+        target: u8,
+        first: u64,
+    ) -> (bool, u64, Relevant) {
+        let seat = (leader + position) % 4;
+        let maximising = is_north_south(seat);
+
+        let (mut moves, len) = self.moves(leader, position, trick);
+        let moves = &mut moves[..len];
+        if let Some(idx) = moves.iter().position(|card| *card == first) {
+            moves[..=idx].rotate_right(1);
+        }
+
+        let (mut relevant, mut skipped) = (0, 0);
+        for card in moves.iter().copied() {
+            if card & skipped != 0 {
+                continue;
+            }
+            self.hands[seat] &= !card;
+            trick[position] = card;
+
+            let (result, depends) = if position == 3 {
+                let winner = self.winner(leader, trick);
+                let won = is_north_south(winner) as u8;
+                let (result, depends) = self.search(winner, target - won.min(target));
+                let winning = trick[(winner + 4 - leader) % 4];
+                (result, depends | self.won_by_rank(trick, winning))
+            } else {
+                let (result, _, depends) = self.play(leader, position + 1, trick, target, 0);
+                (result, depends)
+            };
+
+            self.hands[seat] |= card;
+
+            if result == maximising {
+                return (result, card, depends);
+            }
+            relevant |= depends;
+            // Any lower card of the suit than those the result depends on does the same.
+            if card & depends == 0 {
+                skipped |= self.hands[seat] & (LANE << (suit_of(card) * 16)) & !depends;
+            }
+        }
+
+        let played = trick[..position].iter().fold(0, |cards, card| cards | card);
+        (
+            !maximising,
+            0,
+            self.with_equivalents(seat, played, relevant),
+        )
+    }
+
+    /// Returns the card which won a trick if it did so by outranking another card of its
+    /// suit, along with every card above it.
+    fn won_by_rank(&self, trick: &[u64; 4], winning: u64) -> Relevant {
+        let outranked = trick
+            .iter()
+            .any(|card| *card != winning && suit_of(*card) == suit_of(winning));
+        if outranked {
+            and_above(winning)
+        } else {
+            0
+        }
+    }
+
+    /// Extends the relevant cards after trying every move for `seat`. Only one card of
+    /// each run of equivalent cards is tried, so a run which straddles the relevant cards
+    /// must become relevant entirely to remain a run in other positions.
+    fn with_equivalents(&self, seat: usize, played: u64, relevant: Relevant) -> Relevant {
+        let live = self.live() | played;
+        let mut extended = relevant;
+        for suit in 0..4 {
+            let held = lane(self.hands[seat], suit);
+            let live = lane(live, suit);
+            let mut relevant = lane(relevant, suit);
+            while live & relevant != 0 && live & !relevant != 0 {
+                let lowest = live & relevant & (live & relevant).wrapping_neg();
+                let next = top(live & !relevant);
+                if held & lowest == 0 || held & next == 0 {
+                    break;
+                }
+                relevant = !(next - 1) & LANE;
+            }
+            extended |= relevant << (suit * 16);
+        }
+        extended
+    }
+
+    /// Returns the seat which wins a trick led by `leader`.
+    fn winner(&self, leader: usize, trick: &[u64; 4]) -> usize {
+        let mut best = 0;
+        for position in 1..4 {
+            let (card, winning) = (trick[position], trick[best]);
+            let beats = if suit_of(card) == suit_of(winning) {
+                card > winning
+            } else {
+                Some(suit_of(card)) == self.trumps
+            };
+            if beats {
+                best = position;
+            }
+        }
+        (leader + best) % 4
+    }
+
+    /// Returns the cards worth trying for the seat on turn, best first, and how many there
+    /// are. Of cards which are equivalent (adjacent in rank once the cards already played
+    /// are discounted), only one is returned.
+    fn moves(&self, leader: usize, position: usize, trick: &[u64; 4]) -> ([u64; 13], usize) {
+        let seat = (leader + position) % 4;
+        let hand = self.hands[seat];
+        let played = trick[..position].iter().fold(0, |cards, card| cards | card);
+        let live = self.hands.iter().fold(played, |cards, hand| cards | hand);
+
+        let led = (position > 0).then(|| suit_of(trick[0]));
+        let suits = match led {
+            Some(led) if lane(hand, led) != 0 => led..led + 1,
+            _ => 0..4,
+        };
+
+        // The suit led, the card currently winning the trick, and whether it belongs to our
+        // side.
+        let winning = (position > 0).then(|| {
+            let mut partial = [trick[0]; 4];
+            partial[..position].copy_from_slice(&trick[..position]);
+            let winner = self.winner(leader, &partial);
+            (
+                suit_of(trick[0]),
+                trick[(winner + 4 - leader) % 4],
+                is_north_south(winner) == is_north_south(seat),
+            )
+        });
+
+        let (mut scored, mut len) = ([(0, 0); 13], 0);
+        for suit in suits {
+            let held = lane(hand, suit);
+            let live = lane(live, suit);
+            let mut cards = held;
+            while cards != 0 {
+                let card = cards & cards.wrapping_neg();
+                cards &= !card;
+
+                // Skip the card if the next live card above it is ours too.
+                let above = live & !((card << 1) - 1);
+                if above & above.wrapping_neg() & held != 0 {
+                    continue;
+                }
+
+                let card = card << (suit * 16);
+                scored[len] = (self.score(leader, position, card, winning, live), card);
+                len += 1;
+            }
+        }
+
+        scored[..len].sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        (scored.map(|(_, card)| card), len)
+    }
+
+    /// A heuristic score for playing `card`, used to try the most promising cards first.
+    fn score(
+        &self,
+        leader: usize,
+        position: usize,
+        card: u64,
+        winning: Option<(usize, u64, bool)>,
+        live: u64,
+    ) -> i32 {
+        let seat = (leader + position) % 4;
+        let suit = suit_of(card);
+        let rank = card.trailing_zeros() as i32 % 16;
+        let trumps = (Some(suit) == self.trumps) as i32;
+        let is_top = live & !((card >> (suit * 16) << 1) - 1) == 0;
+
+        let Some((led, current, ours)) = winning else {
+            let partner = self.hands[(seat + 2) % 4];
+            let partner_top = lane(partner, suit) != 0
+                && lane(partner, suit).leading_zeros() <= live.leading_zeros();
+            let ruffs = |hand: u64| match self.trumps {
+                Some(trumps) if trumps != suit => lane(hand, suit) == 0 && lane(hand, trumps) != 0,
+                _ => false,
+            };
+            let opponents_ruff =
+                ruffs(self.hands[(seat + 1) % 4]) || ruffs(self.hands[(seat + 3) % 4]);
+
+            let bit = card >> (suit * 16);
+            let ours = lane(self.hands[seat] | partner, suit);
+            let theirs = live & !ours;
+            let below = live & (bit - 1);
+            let next_ours = below == 0 || ours & top(below) != 0;
+            let above = live & !((bit << 1) - 1);
+            let second = above.count_ones() == 1 && ours & above == 0;
+            let long = ours.count_ones() as i32 - theirs.count_ones() as i32;
+            return match (is_top, partner_top, opponents_ruff) {
+                (_, _, true) => -100 - rank,
+                (true, _, false) if next_ours || theirs == 0 => 300 + rank,
+                (true, _, false) => 50 + rank + 5 * long,
+                (false, true, false) => 200 - rank,
+                _ if ruffs(partner) => 150 - rank,
+                _ if second && next_ours => 120 + rank,
+                _ => 5 * long - rank,
+            };
+        };
+
+        let beats = |card: u64, winning: u64| {
+            if suit_of(card) == suit_of(winning) {
+                card > winning
+            } else {
+                suit_of(card) == self.trumps.unwrap_or(4)
+            }
+        };
+        let (winner, ours) = match beats(card, current) {
+            true => (card, true),
+            false => (current, ours),
+        };
+
+        // Whether an opponent still to play could take the trick from our side.
+        let threatened = (position + 1..4)
+            .map(|position| (leader + position) % 4)
+            .filter(|opponent| is_north_south(*opponent) != is_north_south(seat))
+            .any(|opponent| {
+                let hand = self.hands[opponent];
+                let follows = hand & (LANE << (led * 16));
+                match self.trumps {
+                    _ if follows != 0 => top(follows) > winner && suit_of(winner) == led,
+                    Some(trumps) => {
+                        let ruffs = hand & (LANE << (trumps * 16));
+                        ruffs != 0 && (suit_of(winner) != trumps || top(ruffs) > winner)
+                    }
+                    None => false,
+                }
+            });
+
+        match (ours, threatened, winner == card) {
+            (true, false, true) => 300 - rank - 20 * trumps,
+            (true, false, false) => 200 - rank - 20 * trumps,
+            (true, true, true) => 50 - rank,
+            _ => -rank - 20 * trumps,
+        }
+    }
+}
+
+/// Returns the number of tricks each side takes with perfect play, indexed by side, when
+/// the given hands (indexed by seat) are played in `strain` with `leader` on lead.
+///
+/// # Panics
+///
+/// Panics if the hands don't all hold the same number of cards.
+pub fn solve(hands: &[Hand; 4], strain: BiddingSuit, leader: Seat) -> [u8; 2] {
+    let mut solver = Solver::new(hands, strain);
+    let north_south = solver.north_south_tricks(leader as usize, None);
+    let mut tricks = [0; 2];
+    tricks[Side::NorthSouth as usize] = north_south;
+    tricks[Side::EastWest as usize] = solver.remaining() - north_south;
+    tricks
+}
+
+/// Returns the number of tricks the side on turn takes with perfect play after each card
+/// the seat on turn may play, counting the current trick. The hands (indexed by seat) hold
+/// the cards not yet played, and `played` the cards played so far to the current trick,
+/// which `leader` led.
+///
+/// # Panics
+///
+/// Panics if the trick is complete, or the hands don't hold the same number of cards once
+/// the cards played to it are counted.
+pub fn solve_plays(
+    hands: &[Hand; 4],
+    strain: BiddingSuit,
+    leader: Seat,
+    played: &[Card],
+) -> Vec<(Card, u8)> {
+    assert!(played.len() < 4, "the trick must not be complete");
+    let held = |position: usize| {
+        hands[(leader + position) as usize].len() + (position < played.len()) as usize
+    };
+    assert!(
+        (0..4).all(|position| held(position) == held(0)),
+        "hands must all hold the same number of cards"
+    );
+
+    let mut solver = Solver::unchecked(hands, strain);
+    let mut trick = [0; 4];
+    for (position, card) in played.iter().enumerate() {
+        trick[position] = CardSet::from_iter([*card]).bits();
+    }
+    let seat = leader + played.len();
+    let remaining = hands[seat as usize].len() as u8;
+    solver
+        .north_south_tricks_after(leader as usize, played.len(), &mut trick)
+        .into_iter()
+        .map(|(card, north_south)| {
+            let card = CardSet::from_bits(card).highest().unwrap();
+            match seat.side() {
+                Side::NorthSouth => (card, north_south),
+                Side::EastWest => (card, remaining - north_south),
+            }
+        })
+        .collect()
+}
+
+/// Every strain, in the order of their indices in a [`Table`].
+pub(crate) const STRAINS: [BiddingSuit; 5] = [
+    BiddingSuit::Suit(Suit::Clubs),
+    BiddingSuit::Suit(Suit::Diamonds),
+    BiddingSuit::Suit(Suit::Hearts),
+    BiddingSuit::Suit(Suit::Spades),
+    BiddingSuit::NoTrumps,
+];
+
+fn strain_index(strain: BiddingSuit) -> usize {
+    match strain {
+        BiddingSuit::Suit(suit) => suit as usize,
+        BiddingSuit::NoTrumps => 4,
+    }
+}
+
+/// The number of tricks each player would take as declarer in each strain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Table(pub(crate) [[u8; 4]; 5]);
+
+impl Table {
+    /// Solves the given hands (indexed by seat) in every strain for every declarer.
+    pub fn new(hands: &[Hand; 4]) -> Self {
+        let mut table = [[0; 4]; 5];
+
+        std::thread::scope(|scope| {
+            let handles: Vec<_> = STRAINS
+                .into_iter()
+                .map(|strain| {
+                    scope.spawn(move || {
+                        // The solver's transposition table holds for any leader, so
+                        // sharing it saves most of the work after the first search.
+                        let mut solver = Solver::new(hands, strain);
+                        // The leaders tend to hold declarer to much the same number of
+                        // tricks.
+                        let mut guess = None;
+                        let tricks = std::array::from_fn(|declarer| {
+                            let north_south = solver.north_south_tricks((declarer + 1) % 4, guess);
+                            guess = Some(north_south);
+                            match is_north_south(declarer) {
+                                true => north_south,
+                                false => solver.remaining() - north_south,
+                            }
+                        });
+                        (strain, tricks)
+                    })
+                })
+                .collect();
+
+            for handle in handles {
+                let (strain, tricks) = handle.join().unwrap();
+                table[strain_index(strain)] = tricks;
+            }
+        });
+
+        Self(table)
+    }
+
+    pub fn tricks(&self, strain: BiddingSuit, declarer: Seat) -> u8 {
+        self.0[strain_index(strain)][declarer as usize]
+    }
+}
+
+impl Display for Table {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut rows = vec![format!(
+            "  {}",
+            STRAINS
+                .iter()
+                .rev()
+                .map(|strain| format!("{:>3}", strain.to_string()))
+                .collect::<String>()
+        )];
+        for seat in [Seat::North, Seat::South, Seat::East, Seat::West] {
+            rows.push(format!(
+                "{} {}",
+                char::from(seat),
+                STRAINS
+                    .iter()
+                    .rev()
+                    .map(|strain| format!("{:>3}", self.tricks(*strain, seat)))
+                    .collect::<String>()
+            ));
+        }
+        f.write_str(&rows.join("\n"))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Parses hands in PBN's dot notation, starting with North. Unlike a PBN deal, the
+    /// hands needn't hold 13 cards.
+    fn deal(pbn: &str) -> [Hand; 4] {
+        let hands: Vec<Hand> = pbn[2..]
+            .split_whitespace()
+            .map(|hand| hand.parse().unwrap())
+            .collect();
+        hands.try_into().unwrap()
+    }
+
+    #[test]
+    fn endings() {
+        // South cashes the spade ace, and West can't guard both hearts and clubs.
+        let squeeze = deal("N:.AQ..3 ...432 A.2..Q .KJ..K");
+        assert_eq!([3, 0], solve(&squeeze, BiddingSuit::NoTrumps, Seat::South));
+        assert_eq!([2, 1], solve(&squeeze, BiddingSuit::NoTrumps, Seat::West));
+
+        // North ruffs the spade ace at hearts, but both spades cash at no trumps.
+        let ruff = deal("N:.2.2. AK... ..43. ...32");
+        assert_eq!([0, 2], solve(&ruff, BiddingSuit::NoTrumps, Seat::East));
+        assert_eq!(
+            [2, 0],
+            solve(&ruff, BiddingSuit::Suit(Suit::Hearts), Seat::East)
+        );
+    }
+
+    #[test]
+    fn finesse() {
+        // South leads towards North's AQ: the finesse works when West holds the king,
+        // and fails when East does.
+        let onside = deal("N:AQ... 32... 54... K6...");
+        assert_eq!([2, 0], solve(&onside, BiddingSuit::NoTrumps, Seat::South));
+
+        let offside = deal("N:AQ... K6... 54... 32...");
+        assert_eq!([1, 1], solve(&offside, BiddingSuit::NoTrumps, Seat::South));
+    }
+
+    #[test]
+    fn part_played_tricks() {
+        // South leads a spade and West plays low: North's queen wins the finesse, while
+        // the ace leaves West's king to take the second trick.
+        let hands = deal("N:AQ... 32... 4... K...");
+        let played = ["5S".parse().unwrap(), "6S".parse().unwrap()];
+        assert_eq!(
+            vec![("QS".parse().unwrap(), 2), ("AS".parse().unwrap(), 1)],
+            solve_plays(&hands, BiddingSuit::NoTrumps, Seat::South, &played)
+        );
+
+        // On lead, East takes no tricks whichever card it leads.
+        let hands = deal("N:AQ... 32... 54... K6...");
+        assert!(solve_plays(&hands, BiddingSuit::NoTrumps, Seat::East, &[])
+            .iter()
+            .all(|(_, tricks)| *tricks == 0));
+    }
+
+    #[test]
+    fn full_deal() {
+        // An unshuffled deck gives each seat a whole suit.
+        let hands = crate::deck::Deck::default().deal();
+
+        assert_eq!(
+            [0, 13],
+            solve(&hands, BiddingSuit::Suit(Suit::Spades), Seat::North)
+        );
+        assert_eq!([13, 0], solve(&hands, BiddingSuit::NoTrumps, Seat::North));
+        assert_eq!([0, 13], solve(&hands, BiddingSuit::NoTrumps, Seat::East));
+    }
+
+    #[test]
+    fn table() {
+        let hands = deal("N:AKQ2.J54.T9.8762 JT9.AKQ.AKQJ.AKQ 876.T9876.8765.J 543.32.432.T9543");
+        let table = Table::new(&hands);
+
+        for strain in STRAINS {
+            for seat in [Seat::North, Seat::East, Seat::South, Seat::West] {
+                let leader = seat + 1;
+                assert_eq!(
+                    solve(&hands, strain, leader)[seat.side() as usize],
+                    table.tricks(strain, seat)
+                );
+            }
+        }
+    }
+
+    #[test]
+    #[cfg_attr(
+        debug_assertions,
+        ignore = "takes minutes unoptimised; run with --release"
+    )]
+    fn speed() {
+        // Most tables take well under a second, but those of seeds 2, 12 and 16 take
+        // around five.
+        let budget = std::time::Duration::from_secs(10);
+        for seed in 0..20 {
+            let hands = crate::deck::Deck::from_seed(seed).deal();
+            let start = std::time::Instant::now();
+            Table::new(&hands);
+            let elapsed = start.elapsed();
+            assert!(elapsed < budget, "seed {seed} took {elapsed:?}");
+        }
+    }
+}
@@ -0,0 +1,223 @@
+use std::{
+    fmt::Display,
+    ops::{Deref, Index},
+};
+
+use crate::{
+    auction::Seat,
+    card::{Card, Suit},
+    cardset::CardSet,
+    error::DealError,
+    hand::Hand,
+};
+
+/// The four hands of a deal, indexed by seat, which between them hold each card of the
+/// deck exactly once.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deal([Hand; 4]);
+
+impl TryFrom<[Hand; 4]> for Deal {
+    type Error = DealError;
+
+    fn try_from(value: [Hand; 4]) -> Result<Self, Self::Error> {
+        if value.iter().any(|hand| hand.len() != 13) {
+            return Err(DealError::DealNotComplete);
+        }
+        let held = value
+            .iter()
+            .fold(CardSet::EMPTY, |held, hand| held | hand.cards());
+        if held != CardSet::ALL {
+            return Err(DealError::CardDealtTwice);
+        }
+        Ok(Self(value))
+    }
+}
+
+impl From<Deal> for [Hand; 4] {
+    fn from(value: Deal) -> Self {
+        value.0
+    }
+}
+
+impl Deref for Deal {
+    type Target = [Hand; 4];
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Index<Seat> for Deal {
+    type Output = Hand;
+
+    fn index(&self, index: Seat) -> &Self::Output {
+        &self.0[index as usize]
+    }
+}
+
+impl Deal {
+    /// Wraps hands which are known to partition the deck.
+    pub(crate) fn new_unchecked(hands: [Hand; 4]) -> Self {
+        Self(hands)
+    }
+
+    /// Returns the seat holding the given card.
+    pub fn holder(&self, card: Card) -> Seat {
+        let idx = self.0.iter().position(|hand| hand.contains(&card)).unwrap();
+        Seat::from_repr(idx).unwrap()
+    }
+
+    /// Moves each hand `seats` seats clockwise round the table, so that rotating by
+    /// `Seat::South - seat` puts the hand held in `seat` in the South seat.
+    pub fn rotate(&mut self, seats: usize) {
+        self.0.rotate_right(seats % 4);
+    }
+
+    /// Exchanges the hands held in two seats.
+    pub fn swap(&mut self, lhs: Seat, rhs: Seat) {
+        self.0.swap(lhs as usize, rhs as usize);
+    }
+
+    /// Replaces the spades, hearts, diamonds and clubs of every hand with the cards of the
+    /// same ranks in the corresponding suit of `suits`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `suits` names a suit more than once.
+    pub fn permute_suits(&mut self, suits: [Suit; 4]) {
+        let from = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs];
+        assert!(
+            from.iter().all(|suit| suits.contains(suit)),
+            "suits must be a permutation"
+        );
+        let map = |suit: Suit| suits[from.iter().position(|other| *other == suit).unwrap()];
+        for hand in &mut self.0 {
+            *hand = hand
+                .iter()
+                .map(|card| Card {
+                    suit: map(card.suit),
+                    rank: card.rank,
+                })
+                .collect::<CardSet>()
+                .into();
+        }
+    }
+}
+
+impl Display for Deal {
+    /// Writes the deal as a diagram, with North at the top and West on the left.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let suits = |seat: Seat| {
+            [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs]
+                .map(|suit| self[seat].display_suit(suit))
+        };
+        let [north, east, south, west] =
+            [Seat::North, Seat::East, Seat::South, Seat::West].map(suits);
+
+        // East's hand starts after the longest of West's suits, and North's and South's
+        // hands are indented to sit between the two.
+        let width = west
+            .iter()
+            .map(|line| line.chars().count())
+            .max()
+            .unwrap()
+            .max(12)
+            + 4;
+        let indent = width / 2;
+
+        for line in &north {
+            writeln!(f, "{:indent$}{}", "", line)?;
+        }
+        for (west, east) in west.iter().zip(&east) {
+            let padding = width - west.chars().count();
+            writeln!(f, "{}{:padding$}{}", west, "", east)?;
+        }
+        for (idx, line) in south.iter().enumerate() {
+            write!(f, "{:indent$}{}", "", line)?;
+            if idx < 3 {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::deck::Deck;
+
+    fn deal() -> Deal {
+        let hands = [
+            "AKQJ.T98.765.432",
+            "T98.765.432.AKQJ",
+            "765.432.AKQJ.T98",
+            "432.AKQJ.T98.765",
+        ]
+        .map(|hand| hand.parse().unwrap());
+        Deal::try_from(hands).unwrap()
+    }
+
+    #[test]
+    fn validation() {
+        assert!(Deal::try_from(<[Hand; 4]>::from(Deck::default().deal())).is_ok());
+
+        let mut hands: [Hand; 4] = deal().into();
+        hands[0] = hands[1].clone();
+        assert_eq!(
+            Err(DealError::CardDealtTwice),
+            Deal::try_from(hands.clone())
+        );
+        hands[0] = "AKQJ.T98.765.43".parse().unwrap();
+        assert_eq!(Err(DealError::DealNotComplete), Deal::try_from(hands));
+    }
+
+    #[test]
+    fn rotation_and_swapping() {
+        let original = deal();
+        let mut deal = original.clone();
+        deal.rotate(Seat::South - Seat::North);
+        assert_eq!(original[Seat::North], deal[Seat::South]);
+        assert_eq!(original[Seat::West], deal[Seat::East]);
+        deal.rotate(2);
+        assert_eq!(original, deal);
+
+        deal.swap(Seat::East, Seat::West);
+        assert_eq!(original[Seat::West], deal[Seat::East]);
+        assert_eq!(original[Seat::East], deal[Seat::West]);
+        assert_eq!(Seat::West, deal.holder("AC".parse().unwrap()));
+    }
+
+    #[test]
+    fn suit_permutation() {
+        let mut deal = deal();
+        deal.permute_suits([Suit::Hearts, Suit::Spades, Suit::Diamonds, Suit::Clubs]);
+        assert_eq!(Ok(deal[Seat::North].clone()), "T98.AKQJ.765.432".parse());
+        assert!(Deal::try_from(<[Hand; 4]>::from(deal)).is_ok());
+    }
+
+    #[test]
+    #[should_panic]
+    fn suit_permutation_repeating_a_suit() {
+        deal().permute_suits([Suit::Spades, Suit::Spades, Suit::Diamonds, Suit::Clubs]);
+    }
+
+    #[test]
+    fn diagram() {
+        assert_eq!(
+            "        ♠ A K Q J\n\
+             \x20       ♥ 10 9 8\n\
+             \x20       ♦ 7 6 5\n\
+             \x20       ♣ 4 3 2\n\
+             ♠ 4 3 2         ♠ 10 9 8\n\
+             ♥ A K Q J       ♥ 7 6 5\n\
+             ♦ 10 9 8        ♦ 4 3 2\n\
+             ♣ 7 6 5         ♣ A K Q J\n\
+             \x20       ♠ 7 6 5\n\
+             \x20       ♥ 4 3 2\n\
+             \x20       ♦ A K Q J\n\
+             \x20       ♣ 10 9 8",
+            deal().to_string()
+        );
+    }
+}
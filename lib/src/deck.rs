@@ -1,11 +1,16 @@
-use rand::seq::SliceRandom;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
 use strum::IntoEnumIterator;
 
 use crate::{
     card::{Card, Rank, Suit},
+    deal::Deal,
+    error::DealError,
     hand::Hand,
 };
 
+/// The number of distinct deals, 52! / (13!)^4, which needs 96 bits.
+pub const DEALS: u128 = 53_644_737_765_488_792_839_237_440_000;
+
 #[derive(Debug)]
 pub struct Deck(Vec<Card>);
 
@@ -24,17 +29,129 @@ impl Default for Deck {
 }
 
 impl Deck {
+    /// Returns a deck shuffled by a generator seeded with `seed`, so that the same seed
+    /// always gives the same deck (for a given version of the `rand` crate).
+    pub fn from_seed(seed: u64) -> Self {
+        let mut deck = Self::default();
+        deck.shuffle_with(&mut StdRng::seed_from_u64(seed));
+        deck
+    }
+
     pub fn shuffle(&mut self) {
-        let mut rng = rand::rng();
-        self.0.shuffle(&mut rng);
+        self.shuffle_with(&mut rand::rng());
     }
 
-    pub fn deal(mut self) -> [Hand; 4] {
-        [
+    pub fn shuffle_with<R: Rng + ?Sized>(&mut self, rng: &mut R) {
+        self.0.shuffle(rng);
+    }
+
+    pub fn deal(mut self) -> Deal {
+        Deal::new_unchecked([
             Hand::from(self.0.split_off(13 * 3)),
             Hand::from(self.0.split_off(13 * 2)),
             Hand::from(self.0.split_off(13)),
             Hand::from(self.0),
-        ]
+        ])
+    }
+}
+
+/// Returns the number of the deal among all [`DEALS`] possible deals, from zero, given the
+/// hands indexed by seat. The numbering is in the manner of Richard Pavlicek's: each card
+/// in turn narrows down the range of numbers to the deals in which its holder holds it.
+/// The cards are taken in the order of [`Deck::default`], from the spade two up, so the
+/// numbers don't match his published ones, which take the spade ace first and count
+/// from one.
+pub fn deal_number(hands: &[Hand; 4]) -> Result<u128, DealError> {
+    if hands.iter().any(|hand| hand.len() != 13) {
+        return Err(DealError::DealNotComplete);
+    }
+
+    let mut vacancies = [13; 4];
+    let mut range = DEALS;
+    let mut number = 0;
+    for (dealt, card) in Deck::default().0.iter().enumerate() {
+        let holder = hands
+            .iter()
+            .position(|hand| hand.contains(card))
+            .ok_or(DealError::DealNotComplete)?;
+        let remaining = 52 - dealt as u128;
+
+        // Among the deals left in the range, the share in which a seat holds the card is
+        // in proportion to the seat's vacancies.
+        for vacant in &vacancies[..holder] {
+            number += range * vacant / remaining;
+        }
+        range = range * vacancies[holder] / remaining;
+        vacancies[holder] -= 1;
+    }
+    Ok(number)
+}
+
+/// Returns the deal with the given number (see [`deal_number`]).
+pub fn from_deal_number(mut number: u128) -> Result<Deal, DealError> {
+    if number >= DEALS {
+        return Err(DealError::NumberOutOfRange);
+    }
+
+    let mut hands: [Vec<Card>; 4] = Default::default();
+    let mut vacancies = [13; 4];
+    let mut range = DEALS;
+    for (dealt, card) in Deck::default().0.into_iter().enumerate() {
+        let remaining = 52 - dealt as u128;
+        let mut holder = 0;
+        loop {
+            let share = range * vacancies[holder] / remaining;
+            if number < share {
+                range = share;
+                break;
+            }
+            number -= share;
+            holder += 1;
+        }
+        hands[holder].push(card);
+        vacancies[holder] -= 1;
+    }
+    Ok(Deal::new_unchecked(hands.map(Hand::from)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn seeded_shuffle() {
+        assert_eq!(Deck::from_seed(7).0, Deck::from_seed(7).0);
+        assert_ne!(Deck::from_seed(7).0, Deck::from_seed(8).0);
+
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut deck = Deck::default();
+        deck.shuffle_with(&mut rng);
+        assert_eq!(Deck::from_seed(7).0, deck.0);
+    }
+
+    #[test]
+    fn deal_numbers() {
+        for number in [0, 1, 1 << 64, DEALS / 3, DEALS - 1] {
+            let hands = from_deal_number(number).unwrap();
+            assert!(hands.iter().all(|hand| hand.len() == 13));
+            assert_eq!(Ok(number), deal_number(&hands));
+        }
+        for seed in 0..20 {
+            let hands = Deck::from_seed(seed).deal();
+            let number = deal_number(&hands).unwrap();
+            assert_eq!(Ok(hands), from_deal_number(number));
+        }
+
+        // The first deal gives each seat in turn the next 13 cards of the deck.
+        let first = from_deal_number(0).unwrap();
+        assert_eq!(
+            13,
+            first[crate::auction::Seat::North].suit(Suit::Spades).len()
+        );
+
+        assert_eq!(Err(DealError::NumberOutOfRange), from_deal_number(DEALS));
+        let mut hands: [Hand; 4] = Deck::default().deal().into();
+        hands[0] = hands[1].clone();
+        assert_eq!(Err(DealError::DealNotComplete), deal_number(&hands));
     }
 }
@@ -1,3 +1,5 @@
+use std::fmt::Display;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ParseError {
     BidLevelOutOfBounds,
@@ -7,4 +9,132 @@ pub enum ParseError {
     SuitNotValid,
     RankNotValid,
     SeatNotValid,
+    VulnerabilityNotValid,
+    TagNotValid,
+    DealNotValid,
+    ResultNotValid,
+    CallNotLegal,
+    ShapeNotValid,
+    HandNotValid,
+    CardDuplicated,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BidError {
+    AuctionClosed,
+    InsufficientBid,
+    NothingToDouble,
+    DoubleOfPartner,
+    AlreadyDoubled,
+    NothingToRedouble,
+    RedoubleOfPartner,
+    AlreadyRedoubled,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayError {
+    PlayFinished,
+    CardNotHeld,
+    MustFollowSuit,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RubberError {
+    RubberFinished,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DealError {
+    CardFixedTwice,
+    TooManyCards,
+    AttemptsExhausted,
+    DealNotComplete,
+    NumberOutOfRange,
+    CardDealtTwice,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MovementError {
+    TablesNotValid,
+    PairsNotValid,
+    BoardsNotValid,
+    PairSeatedTwice,
+    BoardPlayedTwice,
+    PairingImpossible,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionError {
+    PairsNotValid,
+    TricksNotValid,
+    ResultDuplicated,
+    NotInMovement,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScriptErrorKind {
+    UnexpectedCharacter(char),
+    UnexpectedToken,
+    UnexpectedEnd,
+    NumberTooLarge,
+    UnknownFunction,
+    SeatNotValid,
+    CardNotValid,
+    ShapeNotValid,
+}
+
+/// An error in a constraint script, at the given (1-based) column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScriptError {
+    pub column: usize,
+    pub kind: ScriptErrorKind,
+}
+
+impl Display for ScriptError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "column {}: ", self.column)?;
+        match self.kind {
+            ScriptErrorKind::UnexpectedCharacter(c) => write!(f, "unexpected character '{c}'"),
+            ScriptErrorKind::UnexpectedToken => f.write_str("unexpected token"),
+            ScriptErrorKind::UnexpectedEnd => f.write_str("unexpected end of script"),
+            ScriptErrorKind::NumberTooLarge => f.write_str("number too large"),
+            ScriptErrorKind::UnknownFunction => f.write_str("unknown function"),
+            ScriptErrorKind::SeatNotValid => f.write_str("expected north, east, south or west"),
+            ScriptErrorKind::CardNotValid => f.write_str("expected a card, such as AS or TC"),
+            ScriptErrorKind::ShapeNotValid => {
+                f.write_str("expected a shape of four lengths or x, such as 4432 or 5xxx")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SystemErrorKind {
+    ArrowMissing,
+    CallNotValid,
+    CallNotLegal,
+    ConstraintNotValid,
+    ExplanationNotValid,
+}
+
+/// An error in a bidding system's rules, at the given (1-based) line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SystemError {
+    pub line: usize,
+    pub kind: SystemErrorKind,
+}
+
+impl Display for SystemError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "line {}: ", self.line)?;
+        f.write_str(match self.kind {
+            SystemErrorKind::ArrowMissing => "expected calls, then -> and a call",
+            SystemErrorKind::CallNotValid => "expected a call, such as 1NT, P, X or XX",
+            SystemErrorKind::CallNotLegal => "the calls aren't a legal auction",
+            SystemErrorKind::ConstraintNotValid => {
+                "expected constraints in brackets, such as [hcp 12-14, spades 5+]"
+            }
+            SystemErrorKind::ExplanationNotValid => "expected an explanation in quotes",
+        })
+    }
 }
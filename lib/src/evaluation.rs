@@ -0,0 +1,287 @@
+use strum::IntoEnumIterator;
+
+use crate::{
+    card::{Card, Rank, Suit},
+    hand::Hand,
+};
+
+impl Hand {
+    fn holds(&self, suit: Suit, rank: Rank) -> bool {
+        self.contains(&Card { suit, rank })
+    }
+
+    /// Returns the lengths of the hand's suits, longest first.
+    fn sorted_distribution(&self) -> [usize; 4] {
+        let mut distribution = self.distribution();
+        distribution.sort_by(|a, b| b.cmp(a));
+        distribution
+    }
+
+    /// Returns the losing trick count: in each suit, the number of the top three cards (or
+    /// fewer, in a shorter suit) which aren't the ace, king or queen. The queen only
+    /// counts in a suit of three or more cards, and the king in one of two or more.
+    pub fn losing_trick_count(&self) -> u8 {
+        Suit::iter()
+            .map(|suit| {
+                let top = self.suit(suit).len().min(3);
+                let honours = [Rank::Ace, Rank::King, Rank::Queen][..top]
+                    .iter()
+                    .filter(|rank| self.holds(suit, **rank))
+                    .count();
+                (top - honours) as u8
+            })
+            .sum()
+    }
+
+    /// Returns the number of controls, counting two for each ace and one for each king.
+    pub fn controls(&self) -> u8 {
+        self.iter()
+            .map(|card| match card.rank {
+                Rank::Ace => 2,
+                Rank::King => 1,
+                _ => 0,
+            })
+            .sum()
+    }
+
+    /// Returns the number of quick (defensive) tricks: in each suit, two for the ace and
+    /// king, one and a half for the ace and queen, one for the ace or the king and queen,
+    /// and a half for a king which isn't singleton.
+    pub fn quick_tricks(&self) -> f64 {
+        Suit::iter()
+            .map(|suit| {
+                let [ace, king, queen] =
+                    [Rank::Ace, Rank::King, Rank::Queen].map(|rank| self.holds(suit, rank));
+                match (ace, king, queen) {
+                    (true, true, _) => 2.0,
+                    (true, false, true) => 1.5,
+                    (true, false, false) | (false, true, true) => 1.0,
+                    (false, true, false) if self.suit(suit).len() > 1 => 0.5,
+                    _ => 0.0,
+                }
+            })
+            .sum()
+    }
+
+    /// Returns the distributional points for length: one for each card over four in a
+    /// suit.
+    pub fn length_points(&self) -> u8 {
+        self.distribution()
+            .iter()
+            .map(|length| length.saturating_sub(4) as u8)
+            .sum()
+    }
+
+    /// Returns the distributional points for shortness: three for a void, two for a
+    /// singleton and one for a doubleton.
+    pub fn shortness_points(&self) -> u8 {
+        self.distribution()
+            .iter()
+            .map(|length| 3u8.saturating_sub(*length as u8))
+            .sum()
+    }
+
+    /// Returns the Kaplan-Rubens ("four Cs") evaluation of the hand, which is on the same
+    /// scale as high card points. For each suit it counts:
+    ///
+    /// - honours: 3 for the ace, 2 for the king (a half if singleton), and for the queen 1
+    ///   with a higher honour in a suit of three or more, 0.75 without, 0.5 in a doubleton
+    ///   with a higher honour, 0.25 without, and nothing singleton. The jack counts 0.5
+    ///   with two higher honours and 0.25 with one, and the ten 0.25 with two higher
+    ///   honours or the jack and one higher honour;
+    /// - suit quality: a tenth of the suit's length for each honour point, counting 4 for
+    ///   the ace, 3 for the king, 2 for the queen and 1 for the jack, with 1 for the ten
+    ///   alongside two higher honours or the jack (a half otherwise), and a half for the
+    ///   nine alongside two higher honours, the ten or the eight. A suit of seven cards or
+    ///   more is rated as if it held the queen, and in one of eight or more the jack too,
+    ///   where they're missing;
+    /// - shortness: 3 for a void, 2 for a singleton and 1 for a doubleton.
+    ///
+    /// One point is taken off the shortness of a hand with any, so the first doubleton is
+    /// free, and half a point off a 4-3-3-3 hand.
+    pub fn kaplan_rubens(&self) -> f64 {
+        // Everything is counted in hundredths of a point.
+        let mut total: i32 = 0;
+        for suit in Suit::iter() {
+            let length = self.suit(suit).len() as i32;
+            let holds = |rank| self.holds(suit, rank);
+            let higher = |ranks: &[Rank]| ranks.iter().filter(|rank| holds(**rank)).count();
+            let above_jack = higher(&[Rank::Ace, Rank::King, Rank::Queen]);
+
+            if holds(Rank::Ace) {
+                total += 300;
+            }
+            if holds(Rank::King) {
+                total += if length == 1 { 50 } else { 200 };
+            }
+            if holds(Rank::Queen) {
+                let with_higher = holds(Rank::Ace) || holds(Rank::King);
+                total += match (length, with_higher) {
+                    (1, _) => 0,
+                    (2, true) => 50,
+                    (2, false) => 25,
+                    (_, true) => 100,
+                    (_, false) => 75,
+                };
+            }
+            if holds(Rank::Jack) {
+                total += match above_jack {
+                    0 => 0,
+                    1 => 25,
+                    _ => 50,
+                };
+            }
+            if holds(Rank::Ten) && (above_jack >= 2 || (holds(Rank::Jack) && above_jack >= 1)) {
+                total += 25;
+            }
+
+            // The quality of the suit, in half honour points, with a long suit's missing
+            // queen and jack counted as held.
+            let rated = |rank| match rank {
+                Rank::Queen => holds(rank) || length >= 7,
+                Rank::Jack => holds(rank) || length >= 8,
+                _ => holds(rank),
+            };
+            let above_ten = [Rank::Ace, Rank::King, Rank::Queen, Rank::Jack]
+                .into_iter()
+                .filter(|rank| rated(*rank))
+                .count();
+            let mut quality = [
+                (Rank::Ace, 8),
+                (Rank::King, 6),
+                (Rank::Queen, 4),
+                (Rank::Jack, 2),
+            ]
+            .iter()
+            .filter(|(rank, _)| rated(*rank))
+            .map(|(_, halves)| halves)
+            .sum::<i32>();
+            if holds(Rank::Ten) {
+                quality += if above_ten >= 2 || rated(Rank::Jack) {
+                    2
+                } else {
+                    1
+                };
+            }
+            if holds(Rank::Nine) && (above_ten >= 2 || holds(Rank::Ten) || holds(Rank::Eight)) {
+                quality += 1;
+            }
+            total += length * quality * 5;
+        }
+
+        let shortness = self.shortness_points() as i32;
+        if shortness > 0 {
+            total += (shortness - 1) * 100;
+        }
+        if self.sorted_distribution() == [4, 3, 3, 3] {
+            total -= 50;
+        }
+        total as f64 / 100.0
+    }
+
+    /// Returns the Zar points of the hand: high card points counting 6 for an ace, 4 for a
+    /// king, 2 for a queen and 1 for a jack, plus the lengths of the two longest suits and
+    /// the difference between the longest and the shortest.
+    pub fn zar_points(&self) -> u8 {
+        let [a, b, _, d] = self.sorted_distribution();
+        self.hcp() + self.controls() + (a + b + a - d) as u8
+    }
+
+    /// Returns whether the hand passes the rule of 20 for opening in first or second seat:
+    /// whether its high card points and the lengths of its two longest suits add up to 20
+    /// or more.
+    pub fn rule_of_20(&self) -> bool {
+        let [a, b, ..] = self.sorted_distribution();
+        self.hcp() as usize + a + b >= 20
+    }
+
+    /// Returns whether the hand passes the rule of 15 for opening in fourth seat: whether
+    /// its high card points and its length in spades add up to 15 or more.
+    pub fn rule_of_15(&self) -> bool {
+        self.hcp() as usize + self.suit(Suit::Spades).len() >= 15
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Returns a hand from its suits, spades first, as ranks separated by spaces.
+    fn hand(suits: [&str; 4]) -> Hand {
+        let mut cards = vec![];
+        for (suit, ranks) in [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs]
+            .into_iter()
+            .zip(suits)
+        {
+            for rank in ranks.chars() {
+                cards.push(Card {
+                    suit,
+                    rank: rank.try_into().unwrap(),
+                });
+            }
+        }
+        Hand::from(cards)
+    }
+
+    // The examples are worked by hand from the methods as first published: the losing
+    // trick count from Ron Klinger's "The Modern Losing Trick Count", the four Cs from
+    // Edgar Kaplan and Jeff Rubens' article in The Bridge World of October 1982, and Zar
+    // points from Zar Petkov's "Zar Points".
+
+    #[test]
+    fn losing_trick_count() {
+        // One loser in AK752, two in K83 and Q4, and three in 962.
+        assert_eq!(8, hand(["AK752", "K83", "Q4", "962"]).losing_trick_count());
+        assert_eq!(8, hand(["Q2", "AQJ4", "KJ3", "8765"]).losing_trick_count());
+        assert_eq!(2, hand(["AKQJT98", "A", "", "K5432"]).losing_trick_count());
+    }
+
+    #[test]
+    fn controls_and_quick_tricks() {
+        assert_eq!(4, hand(["AK752", "K83", "Q4", "962"]).controls());
+        assert_eq!(2.5, hand(["AK752", "K83", "Q4", "962"]).quick_tricks());
+        assert_eq!(2.0, hand(["Q2", "AQJ4", "KJ3", "8765"]).quick_tricks());
+        assert_eq!(3.5, hand(["AKQJT98", "A", "", "K5432"]).quick_tricks());
+        assert_eq!(1.0, hand(["K", "KQ2", "5432", "65432"]).quick_tricks());
+    }
+
+    #[test]
+    fn distributional_points() {
+        let long = hand(["AKQJT98", "A", "", "K5432"]);
+        assert_eq!(4, long.length_points());
+        assert_eq!(5, long.shortness_points());
+    }
+
+    #[test]
+    fn kaplan_rubens() {
+        // Spades: 5 for the ace and king, 7 x 5 / 10 = 3.5 quality. Hearts: 2, and
+        // 3 x 3 / 10 = 0.9. Diamonds: 0.25 for the unsupported doubleton queen, and
+        // 2 x 2 / 10 = 0.4. The doubleton is free.
+        assert_eq!(12.05, hand(["AK752", "K83", "Q4", "962"]).kaplan_rubens());
+        assert_eq!(1.45, hand(["J43", "T98", "8765", "Q32"]).kaplan_rubens());
+
+        // The seven-card spades are rated as if they held the queen: 5.25 for the
+        // honours and ten, and 7 x (4 + 3 + 2 + 1) / 10 = 7 quality, rather than 5.6.
+        // Hearts: 3, and 2 x 4 / 10 = 0.8. Two of the three doubletons count.
+        assert_eq!(18.05, hand(["AKT8642", "A5", "73", "82"]).kaplan_rubens());
+    }
+
+    #[test]
+    fn zar_points() {
+        // 6 + 4 + 4 + 2 = 16 for the honours, 5 + 3 for the two longest suits and 5 - 2
+        // for the spread.
+        assert_eq!(27, hand(["AK752", "K83", "Q4", "962"]).zar_points());
+        assert_eq!(26, hand(["Q2", "AQJ4", "KJ3", "8765"]).zar_points());
+    }
+
+    #[test]
+    fn opening_rules() {
+        let opener = hand(["AK752", "K83", "Q4", "962"]);
+        assert!(opener.rule_of_20());
+        assert!(opener.rule_of_15());
+
+        let passer = hand(["Q2", "AJ84", "K73", "J765"]);
+        assert!(!passer.rule_of_20());
+        assert!(!passer.rule_of_15());
+    }
+}
@@ -0,0 +1,249 @@
+//! Generation of random deals which satisfy constraints on the hands, with some cards or
+//! whole hands fixed in advance.
+
+use std::ops::RangeBounds;
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{
+    auction::Seat,
+    card::{Card, Suit},
+    cardset::CardSet,
+    deal::Deal,
+    error::DealError,
+    hand::Hand,
+    shape::Pattern,
+};
+
+type HandPredicate = Box<dyn Fn(&Hand) -> bool + Send + Sync>;
+type DealPredicate = Box<dyn Fn(&[Hand; 4]) -> bool + Send + Sync>;
+
+/// Deals random hands (indexed by seat) until they satisfy every predicate given to the
+/// generator. Cards fixed in a seat are always dealt to it, and the remaining cards are
+/// dealt at random to fill the hands up to 13 cards each.
+pub struct DealGenerator {
+    fixed: [Vec<Card>; 4],
+    hand_predicates: [Vec<HandPredicate>; 4],
+    deal_predicates: Vec<DealPredicate>,
+    attempts: usize,
+}
+
+impl Default for DealGenerator {
+    fn default() -> Self {
+        Self {
+            fixed: Default::default(),
+            hand_predicates: Default::default(),
+            deal_predicates: vec![],
+            attempts: 1_000_000,
+        }
+    }
+}
+
+impl DealGenerator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fixes the given cards in a seat's hand, along with any fixed before.
+    pub fn cards(mut self, seat: Seat, cards: impl IntoIterator<Item = Card>) -> Self {
+        self.fixed[seat as usize].extend(cards);
+        self
+    }
+
+    /// Fixes a seat's whole hand.
+    pub fn hand(self, seat: Seat, hand: &Hand) -> Self {
+        self.cards(seat, hand.iter())
+    }
+
+    /// Requires a seat's hand to satisfy `predicate`.
+    pub fn seat(
+        mut self,
+        seat: Seat,
+        predicate: impl Fn(&Hand) -> bool + Send + Sync + 'static,
+    ) -> Self {
+        self.hand_predicates[seat as usize].push(Box::new(predicate));
+        self
+    }
+
+    /// Requires the deal, as hands indexed by seat, to satisfy `predicate`.
+    pub fn deal(mut self, predicate: impl Fn(&[Hand; 4]) -> bool + Send + Sync + 'static) -> Self {
+        self.deal_predicates.push(Box::new(predicate));
+        self
+    }
+
+    /// Sets the number of deals to try before giving up. Defaults to a million.
+    pub fn attempts(mut self, attempts: usize) -> Self {
+        self.attempts = attempts;
+        self
+    }
+
+    /// Generates a deal which satisfies every predicate.
+    pub fn generate(&self) -> Result<Deal, DealError> {
+        self.generate_with(&mut rand::rng())
+    }
+
+    /// Generates a deal which satisfies every predicate, using the given source of
+    /// randomness.
+    pub fn generate_with<R: Rng + ?Sized>(&self, rng: &mut R) -> Result<Deal, DealError> {
+        if self.fixed.iter().any(|cards| cards.len() > 13) {
+            return Err(DealError::TooManyCards);
+        }
+        let mut fixed = CardSet::EMPTY;
+        for card in self.fixed.iter().flatten() {
+            if !fixed.insert(*card) {
+                return Err(DealError::CardFixedTwice);
+            }
+        }
+
+        let mut remaining: Vec<_> = (!fixed).iter().collect();
+
+        // Seats with the fewest cards to deal are checked first, since their predicates
+        // reject a deal most cheaply; a fully fixed hand only needs checking once.
+        let mut order = [Seat::North, Seat::East, Seat::South, Seat::West];
+        order.sort_by_key(|seat| 13 - self.fixed[*seat as usize].len());
+        for seat in order {
+            let cards = &self.fixed[seat as usize];
+            if cards.len() == 13 && !self.satisfies(seat, &Hand::from(cards.clone())) {
+                return Err(DealError::AttemptsExhausted);
+            }
+        }
+
+        'attempt: for _ in 0..self.attempts {
+            remaining.shuffle(rng);
+
+            let mut hands: [Hand; 4] = std::array::from_fn(|_| Hand::from(vec![]));
+            let mut dealt = 0;
+            for seat in order {
+                let mut cards = self.fixed[seat as usize].clone();
+                let needed = 13 - cards.len();
+                if needed == 0 {
+                    hands[seat as usize] = Hand::from(cards);
+                    continue;
+                }
+                cards.extend_from_slice(&remaining[dealt..dealt + needed]);
+                dealt += needed;
+
+                let hand = Hand::from(cards);
+                if !self.satisfies(seat, &hand) {
+                    continue 'attempt;
+                }
+                hands[seat as usize] = hand;
+            }
+
+            if self
+                .deal_predicates
+                .iter()
+                .all(|predicate| predicate(&hands))
+            {
+                return Ok(Deal::new_unchecked(hands));
+            }
+        }
+
+        Err(DealError::AttemptsExhausted)
+    }
+
+    fn satisfies(&self, seat: Seat, hand: &Hand) -> bool {
+        self.hand_predicates[seat as usize]
+            .iter()
+            .all(|predicate| predicate(hand))
+    }
+}
+
+/// Returns a predicate for hands whose high card points lie in `range`.
+pub fn hcp(range: impl RangeBounds<u8> + Send + Sync + 'static) -> impl Fn(&Hand) -> bool {
+    move |hand| range.contains(&hand.hcp())
+}
+
+/// Returns a predicate for hands whose length in `suit` lies in `range`.
+pub fn length(
+    suit: Suit,
+    range: impl RangeBounds<usize> + Send + Sync + 'static,
+) -> impl Fn(&Hand) -> bool {
+    move |hand| range.contains(&hand.suit(suit).len())
+}
+
+/// Returns a predicate for balanced hands: those with no singleton or void, and at most
+/// one doubleton.
+pub fn balanced() -> impl Fn(&Hand) -> bool {
+    |hand| hand.shape().is_balanced()
+}
+
+/// Returns a predicate for hands with the given pattern, in any suits.
+pub fn pattern(pattern: Pattern) -> impl Fn(&Hand) -> bool {
+    move |hand| hand.shape().pattern() == pattern
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::{card::Rank, deck::Deck};
+
+    #[test]
+    fn constraints() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let generator = DealGenerator::new()
+            .seat(Seat::South, hcp(15..=17))
+            .seat(Seat::South, balanced())
+            .seat(Seat::North, length(Suit::Spades, 4..));
+
+        for _ in 0..20 {
+            let hands = generator.generate_with(&mut rng).unwrap();
+            let south = &hands[Seat::South];
+            assert!((15..=17).contains(&south.hcp()));
+            assert!(south.distribution().iter().all(|length| *length >= 2));
+            assert!(hands[Seat::North].suit(Suit::Spades).len() >= 4);
+            assert!(hands.iter().all(|hand| hand.len() == 13));
+        }
+    }
+
+    #[test]
+    fn fixed_cards() {
+        let mut rng = StdRng::seed_from_u64(2);
+        // An unshuffled deck deals North every club.
+        let north = Deck::default().deal()[Seat::North].clone();
+        let ace = Card {
+            suit: Suit::Hearts,
+            rank: Rank::Ace,
+        };
+        let generator = DealGenerator::new()
+            .hand(Seat::North, &north)
+            .cards(Seat::West, [ace])
+            .deal(|hands| hands[Seat::East as usize].hcp() > hands[Seat::South as usize].hcp());
+
+        let hands = generator.generate_with(&mut rng).unwrap();
+        assert_eq!(north, hands[Seat::North]);
+        assert!(hands[Seat::West].contains(&ace));
+        assert!(hands[Seat::East].hcp() > hands[Seat::South].hcp());
+        assert_eq!(52, hands.iter().map(Hand::len).sum::<usize>());
+    }
+
+    #[test]
+    fn patterns() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let generator = DealGenerator::new().seat(Seat::East, pattern("4441".parse().unwrap()));
+
+        let hands = generator.generate_with(&mut rng).unwrap();
+        let mut distribution = hands[Seat::East].distribution();
+        distribution.sort();
+        assert_eq!([1, 4, 4, 4], distribution);
+    }
+
+    #[test]
+    fn errors() {
+        let ace = Card {
+            suit: Suit::Spades,
+            rank: Rank::Ace,
+        };
+        let generator = DealGenerator::new()
+            .cards(Seat::North, [ace])
+            .cards(Seat::East, [ace]);
+        assert_eq!(Err(DealError::CardFixedTwice), generator.generate());
+
+        let generator = DealGenerator::new()
+            .seat(Seat::North, hcp(38..))
+            .attempts(100);
+        assert_eq!(Err(DealError::AttemptsExhausted), generator.generate());
+    }
+}
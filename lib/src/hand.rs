@@ -1,82 +1,128 @@
-use std::fmt::Display;
+use std::{fmt::Display, str::FromStr};
 
-use crate::card::{Card, Suit};
+use crate::{
+    card::{Card, Rank, Suit},
+    cardset::CardSet,
+    error::ParseError,
+};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Hand {
-    spades: Vec<Card>,
-    hearts: Vec<Card>,
-    diamonds: Vec<Card>,
-    clubs: Vec<Card>,
+    cards: CardSet,
 }
 
 impl From<Vec<Card>> for Hand {
     fn from(value: Vec<Card>) -> Self {
-        let (mut spades, mut hearts, mut diamonds, mut clubs) = (vec![], vec![], vec![], vec![]);
-
-        for card in value {
-            match card.suit {
-                crate::card::Suit::Spades => spades.push(card),
-                crate::card::Suit::Hearts => hearts.push(card),
-                crate::card::Suit::Diamonds => diamonds.push(card),
-                crate::card::Suit::Clubs => clubs.push(card),
-            }
+        Self {
+            cards: CardSet::from(value),
         }
+    }
+}
 
-        spades.sort_by(|a, b| b.rank.cmp(&a.rank));
-        hearts.sort_by(|a, b| b.rank.cmp(&a.rank));
-        diamonds.sort_by(|a, b| b.rank.cmp(&a.rank));
-        clubs.sort_by(|a, b| b.rank.cmp(&a.rank));
+impl From<CardSet> for Hand {
+    fn from(value: CardSet) -> Self {
+        Self { cards: value }
+    }
+}
 
-        Self {
-            spades,
-            hearts,
-            diamonds,
-            clubs,
-        }
+impl From<Hand> for CardSet {
+    fn from(value: Hand) -> Self {
+        value.cards
     }
 }
 
 impl Display for Hand {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        fn collect(suit: &[Card]) -> String {
-            if suit.is_empty() {
-                return "—".to_string();
+        let suits = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs]
+            .map(|suit| self.display_suit(suit));
+        f.write_str(&suits.join("  "))
+    }
+}
+
+impl FromStr for Hand {
+    type Err = ParseError;
+
+    /// Parses a hand in PBN's dot notation ("AKQ2.J54.T9.8762", spades first), as suits
+    /// each led by its letter ("S AKQ2 H J54 D T9 C 8762"), or as written by `Display`,
+    /// with suit symbols, "10" for the ten and "—" for a void. Suits left out of the
+    /// latter two are void.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut cards = CardSet::EMPTY;
+        let mut add = |suit, rank| {
+            if cards.insert(Card { suit, rank }) {
+                Ok(())
+            } else {
+                Err(ParseError::CardDuplicated)
+            }
+        };
+
+        if s.contains('.') {
+            let suits: Vec<&str> = s.trim().split('.').collect();
+            if suits.len() != 4 {
+                return Err(ParseError::HandNotValid);
+            }
+            for (suit, ranks) in [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs]
+                .into_iter()
+                .zip(suits)
+            {
+                for rank in ranks.chars() {
+                    add(suit, rank.to_ascii_uppercase().try_into()?)?;
+                }
+            }
+            return Ok(Self::from(cards));
+        }
+
+        let mut suit = None;
+        let mut seen = vec![];
+        let mut chars = s.chars().map(|c| c.to_ascii_uppercase());
+        while let Some(c) = chars.next() {
+            let next = match c {
+                '♠' => Suit::Spades,
+                '♥' => Suit::Hearts,
+                '♦' => Suit::Diamonds,
+                '♣' => Suit::Clubs,
+                '—' | '-' => continue,
+                c if c.is_whitespace() => continue,
+                c => match Suit::try_from(c) {
+                    Ok(next) => next,
+                    Err(_) => {
+                        let rank = match c {
+                            '1' if chars.next() == Some('0') => Rank::Ten,
+                            '1' => return Err(ParseError::RankNotValid),
+                            c => c.try_into()?,
+                        };
+                        add(suit.ok_or(ParseError::HandNotValid)?, rank)?;
+                        continue;
+                    }
+                },
+            };
+            if seen.contains(&next) {
+                return Err(ParseError::HandNotValid);
             }
-            suit.iter()
-                .map(|card| card.rank.to_string())
-                .collect::<Vec<String>>()
-                .join(" ")
+            seen.push(next);
+            suit = Some(next);
         }
-        f.write_str(&format!(
-            "{} {}  {} {}  {} {}  {} {}",
-            Suit::Spades,
-            collect(&self.spades),
-            Suit::Hearts,
-            collect(&self.hearts),
-            Suit::Diamonds,
-            collect(&self.diamonds),
-            Suit::Clubs,
-            collect(&self.clubs),
-        ))
+
+        Ok(Self::from(cards))
     }
 }
 
 impl Hand {
     pub fn len(&self) -> usize {
-        self.distribution().iter().sum()
+        self.cards.len()
     }
 
     pub fn is_empty(&self) -> bool {
-        self.distribution().iter().all(|size| *size == 0)
+        self.cards.is_empty()
     }
 
-    pub fn iter(&self) -> impl Iterator<Item = &Card> {
-        self.spades
-            .iter()
-            .chain(self.hearts.iter())
-            .chain(self.diamonds.iter())
-            .chain(self.clubs.iter())
+    pub fn cards(&self) -> CardSet {
+        self.cards
+    }
+
+    /// Iterates over the cards of the hand, from the highest spade to the lowest club.
+    pub fn iter(&self) -> impl Iterator<Item = Card> {
+        self.cards.iter()
     }
 
     pub fn hcp(&self) -> u8 {
@@ -84,11 +130,88 @@ impl Hand {
     }
 
     pub fn distribution(&self) -> [usize; 4] {
-        [
-            self.spades.len(),
-            self.hearts.len(),
-            self.diamonds.len(),
-            self.clubs.len(),
-        ]
+        [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs]
+            .map(|suit| self.cards.suit_len(suit))
+    }
+
+    pub fn suit(&self, suit: Suit) -> CardSet {
+        self.cards.suit(suit)
+    }
+
+    /// Writes one suit of the hand as `Display` does, after the suit's symbol: "♠ A K Q 2",
+    /// or "♠ —" for a void.
+    pub fn display_suit(&self, suit: Suit) -> String {
+        let cards = self.suit(suit);
+        if cards.is_empty() {
+            return format!("{} —", suit);
+        }
+        let ranks = cards
+            .iter()
+            .map(|card| card.rank.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        format!("{} {}", suit, ranks)
+    }
+
+    pub fn contains(&self, card: &Card) -> bool {
+        self.cards.contains(*card)
+    }
+
+    /// Removes the given card from the hand, returning whether it was held.
+    pub fn remove(&mut self, card: &Card) -> bool {
+        self.cards.remove(*card)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_notations() {
+        let hand: Hand = "AKQ2.J54.T9.8762".parse().unwrap();
+        assert_eq!(13, hand.len());
+        assert_eq!([4, 3, 2, 4], hand.distribution());
+        assert!(hand.contains(&"TD".parse().unwrap()));
+
+        assert_eq!(Ok(hand.clone()), "S AKQ2 H J54 D T9 C 8762".parse());
+        assert_eq!(Ok(hand.clone()), "SAKQ2HJ54DT9C8762".parse());
+        assert_eq!(Ok(hand.clone()), "s akq2 h j54 d t9 c 8762".parse());
+        assert_eq!(Ok(hand.clone()), hand.to_string().parse());
+        assert_eq!(Ok(hand), "♠ A K Q 2  ♥ J 5 4  ♦ 10 9  ♣ 8 7 6 2".parse());
+    }
+
+    #[test]
+    fn parse_voids() {
+        let hand: Hand = "AKQJ765.AKQ2..32".parse().unwrap();
+        assert_eq!(Ok(hand.clone()), "S AKQJ765 H AKQ2 C 32".parse());
+        assert_eq!(Ok(hand.clone()), hand.to_string().parse());
+        assert!(hand.to_string().contains('—'));
+        assert_eq!("♦ —", hand.display_suit(Suit::Diamonds));
+        assert_eq!("♣ 3 2", hand.display_suit(Suit::Clubs));
+        assert_eq!(Ok(Hand::from(vec![])), "".parse());
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Err(ParseError::CardDuplicated),
+            "AKA.J54.T9.8762".parse::<Hand>()
+        );
+        assert_eq!(Err(ParseError::CardDuplicated), "S AKA H Q".parse::<Hand>());
+        assert_eq!(
+            Err(ParseError::HandNotValid),
+            "AKQ2.J54.T98762".parse::<Hand>()
+        );
+        assert_eq!(Err(ParseError::HandNotValid), "AKQ S2".parse::<Hand>());
+        assert_eq!(
+            Err(ParseError::HandNotValid),
+            "S AK H Q S 2".parse::<Hand>()
+        );
+        assert_eq!(
+            Err(ParseError::RankNotValid),
+            "AKX2.J54.T9.8762".parse::<Hand>()
+        );
+        assert_eq!(Err(ParseError::RankNotValid), "♠ A 1 ♥ 2".parse::<Hand>());
     }
 }
@@ -0,0 +1,303 @@
+//! What the calls of an auction show, according to a bidding system.
+
+use std::{fmt::Display, ops::RangeInclusive};
+
+use crate::{
+    auction::{Auction, AuctionBid},
+    card::Suit,
+    hand::Hand,
+    shape::ShapeClass,
+    system::{Constraint, Force, Rule, System},
+};
+
+const MAX_HCP: u8 = 37;
+const MAX_LENGTH: usize = 13;
+const SUITS: [Suit; 4] = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs];
+
+fn meet<T: Ord + Copy>(lhs: &RangeInclusive<T>, rhs: &RangeInclusive<T>) -> RangeInclusive<T> {
+    *lhs.start().max(rhs.start())..=*lhs.end().min(rhs.end())
+}
+
+fn join<T: Ord + Copy>(lhs: &RangeInclusive<T>, rhs: &RangeInclusive<T>) -> RangeInclusive<T> {
+    *lhs.start().min(rhs.start())..=*lhs.end().max(rhs.end())
+}
+
+/// Writes a range as `12`, `12-14` or `12+`.
+fn range<T: Display + PartialEq>(range: &RangeInclusive<T>, max: T) -> String {
+    if range.start() == range.end() {
+        range.start().to_string()
+    } else if *range.end() == max {
+        format!("{}+", range.start())
+    } else {
+        format!("{}-{}", range.start(), range.end())
+    }
+}
+
+/// What a hand holds, as far as the auction has told: a range of high card points and a
+/// range of lengths for each of spades, hearts, diamonds and clubs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Shown {
+    pub hcp: RangeInclusive<u8>,
+    pub lengths: [RangeInclusive<usize>; 4],
+}
+
+impl Default for Shown {
+    fn default() -> Self {
+        Self {
+            hcp: 0..=MAX_HCP,
+            lengths: [0, 1, 2, 3].map(|_| 0..=MAX_LENGTH),
+        }
+    }
+}
+
+impl Shown {
+    /// Returns what a hand satisfying the rule's constraints must hold. Constraints
+    /// which can't be written as ranges, such as negated ones, tell nothing.
+    fn from_rule(rule: &Rule) -> Self {
+        let mut shown = Self::default();
+        for constraint in rule.constraints() {
+            match constraint {
+                Constraint::Hcp(hcp) => shown.hcp = meet(&shown.hcp, hcp),
+                Constraint::Length(suit, length) => {
+                    let idx = SUITS.iter().position(|other| other == suit).unwrap();
+                    shown.lengths[idx] = meet(&shown.lengths[idx], length);
+                }
+                Constraint::Class(ShapeClass::Balanced) => shown.limit_lengths(2..=5),
+                Constraint::Class(ShapeClass::SemiBalanced) => shown.limit_lengths(2..=7),
+                Constraint::Pattern(pattern) => {
+                    let lengths = pattern.lengths();
+                    shown.limit_lengths(lengths[3]..=lengths[0]);
+                }
+                _ => {}
+            }
+        }
+        shown.hcp = meet(&shown.hcp, &(0..=MAX_HCP));
+        shown.limit_lengths(0..=MAX_LENGTH);
+        shown
+    }
+
+    /// Returns whether a hand holds what's been shown, so that simulations can deal only
+    /// hands which agree with the auction.
+    pub fn admits(&self, hand: &Hand) -> bool {
+        self.hcp.contains(&hand.hcp())
+            && SUITS
+                .iter()
+                .zip(&self.lengths)
+                .all(|(suit, length)| length.contains(&hand.suit(*suit).len()))
+    }
+
+    fn limit_lengths(&mut self, limit: RangeInclusive<usize>) {
+        for length in &mut self.lengths {
+            *length = meet(length, &limit);
+        }
+    }
+
+    /// Returns what's shown by either of two calls, when it isn't known which was meant.
+    fn or(&self, other: &Self) -> Self {
+        Self {
+            hcp: join(&self.hcp, &other.hcp),
+            lengths: [0, 1, 2, 3].map(|idx| join(&self.lengths[idx], &other.lengths[idx])),
+        }
+    }
+
+    /// Returns what's shown by both the earlier and later calls of a player. Where they
+    /// contradict each other the later call is believed.
+    fn and(&self, later: &Self) -> Self {
+        fn believe<T: Ord + Copy>(
+            earlier: &RangeInclusive<T>,
+            later: &RangeInclusive<T>,
+        ) -> RangeInclusive<T> {
+            let both = meet(earlier, later);
+            if both.is_empty() {
+                later.clone()
+            } else {
+                both
+            }
+        }
+        Self {
+            hcp: believe(&self.hcp, &later.hcp),
+            lengths: [0, 1, 2, 3].map(|idx| believe(&self.lengths[idx], &later.lengths[idx])),
+        }
+    }
+}
+
+impl Display for Shown {
+    /// Writes the ranges which tell something, such as "15-17 HCP, 5+♠".
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut parts = vec![];
+        if self.hcp != (0..=MAX_HCP) {
+            parts.push(format!("{} HCP", range(&self.hcp, MAX_HCP)));
+        }
+        for (suit, length) in SUITS.iter().zip(&self.lengths) {
+            if *length != (0..=MAX_LENGTH) {
+                parts.push(format!("{}{}", range(length, MAX_LENGTH), suit));
+            }
+        }
+        f.write_str(&parts.join(", "))
+    }
+}
+
+/// The meaning of a call in a bidding system.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Description {
+    pub shown: Shown,
+    pub force: Force,
+    pub explanation: Option<String>,
+}
+
+impl Display for Description {
+    /// Writes the explanation followed by what's shown, such as
+    /// "Stayman (8+ HCP, forcing)".
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut shown = self.shown.to_string();
+        if self.force != Force::NonForcing {
+            if !shown.is_empty() {
+                shown.push_str(", ");
+            }
+            shown.push_str(&self.force.to_string());
+        }
+        match (&self.explanation, shown.is_empty()) {
+            (Some(explanation), true) => f.write_str(explanation),
+            (Some(explanation), false) => write!(f, "{} ({})", explanation, shown),
+            (None, _) => f.write_str(&shown),
+        }
+    }
+}
+
+impl System {
+    /// Describes each call of the auction, or gives `None` for calls the system has no
+    /// rule for. When several rules give the same call, it shows only what they have in
+    /// common, and is forcing only if all of them are.
+    pub fn explain(&self, auction: &Auction) -> Vec<Option<Description>> {
+        let sequence = auction.sequence();
+        (0..sequence.len())
+            .map(|idx| {
+                self.candidates_after(&sequence[..idx])
+                    .filter(|rule| rule.call() == sequence[idx])
+                    .fold(None, |description: Option<Description>, rule| {
+                        let shown = Shown::from_rule(rule);
+                        Some(match description {
+                            None => Description {
+                                shown,
+                                force: rule.force(),
+                                explanation: rule.explanation().map(str::to_string),
+                            },
+                            Some(description) => Description {
+                                shown: description.shown.or(&shown),
+                                force: description.force.min(rule.force()),
+                                explanation: description
+                                    .explanation
+                                    .or_else(|| rule.explanation().map(str::to_string)),
+                            },
+                        })
+                    })
+            })
+            .collect()
+    }
+
+    /// Returns what each player, indexed by seat, has shown by their calls so far.
+    pub fn inferences(&self, auction: &Auction) -> [Shown; 4] {
+        let mut shown: [Shown; 4] = Default::default();
+        let mut seat = *auction.dealer();
+        for description in self.explain(auction) {
+            if let Some(description) = description {
+                shown[seat as usize] = shown[seat as usize].and(&description.shown);
+            }
+            seat = seat + 1;
+        }
+        shown
+    }
+
+    /// Returns the description of the call, if it were made next in the auction.
+    pub fn describe(&self, auction: &Auction, call: AuctionBid) -> Option<Description> {
+        let mut auction = auction.clone();
+        auction.enter_bid(call).ok()?;
+        self.explain(&auction).pop().flatten()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::auction::Seat;
+
+    const SYSTEM: &str = "\
+-> 1NT [hcp 15-17, balanced] \"strong notrump\"
+-> 1H [hcp 12-21, hearts 5+]
+1NT P -> 2C [hcp 8+, forcing] \"Stayman\"
+1NT P 2C P -> 2D [spades 0-3, hearts 0-3] \"no four-card major\"
+1NT P 2C P -> 2H [hearts 4-5]
+1H * -> 2H [hcp 6-9, hearts 3+]
+1H * -> 2H [hcp 10-12, hearts 3+, invitational]
+";
+
+    fn auction(calls: &str) -> Auction {
+        let mut auction = Auction::new(Seat::North);
+        for call in calls.split_whitespace() {
+            auction.enter_bid(call.parse().unwrap()).unwrap();
+        }
+        auction
+    }
+
+    #[test]
+    fn explanations() {
+        let system: System = SYSTEM.parse().unwrap();
+        let descriptions: Vec<_> = system
+            .explain(&auction("1NT Pass 2C Pass 2D Pass"))
+            .into_iter()
+            .map(|description| description.map(|description| description.to_string()))
+            .collect();
+        assert_eq!(
+            vec![
+                Some("strong notrump (15-17 HCP, 2-5♠, 2-5♥, 2-5♦, 2-5♣)".to_string()),
+                None,
+                Some("Stayman (8+ HCP, forcing)".to_string()),
+                None,
+                Some("no four-card major (0-3♠, 0-3♥)".to_string()),
+                None,
+            ],
+            descriptions
+        );
+    }
+
+    #[test]
+    fn calls_with_several_meanings() {
+        let system: System = SYSTEM.parse().unwrap();
+        let description = system
+            .describe(&auction("Pass 1H Pass"), "2H".parse().unwrap())
+            .unwrap();
+        assert_eq!(6..=12, description.shown.hcp);
+        assert_eq!(Force::NonForcing, description.force);
+        assert_eq!("6-12 HCP, 3+♥", description.to_string());
+        assert_eq!(
+            None,
+            system.describe(&auction("Pass 1H Pass"), AuctionBid::Pass)
+        );
+    }
+
+    #[test]
+    fn inferences() {
+        let system: System = SYSTEM.parse().unwrap();
+        let shown = system.inferences(&auction("1NT Pass 2C Pass 2D Pass"));
+        assert_eq!(
+            "15-17 HCP, 2-3♠, 2-3♥, 2-5♦, 2-5♣",
+            shown[Seat::North as usize].to_string()
+        );
+        assert_eq!("8+ HCP", shown[Seat::South as usize].to_string());
+        assert_eq!(Shown::default(), shown[Seat::East as usize]);
+    }
+
+    #[test]
+    fn admits() {
+        let system: System = SYSTEM.parse().unwrap();
+        let shown = &system.inferences(&auction("1NT Pass 2C Pass 2D Pass"))[Seat::North as usize];
+
+        // 15 HCP, 3-3-4-3.
+        assert!(shown.admits(&"AQ3.K52.QJ84.K76".parse().unwrap()));
+        // 15 HCP, but four hearts.
+        assert!(!shown.admits(&"AQ3.K542.QJ8.K76".parse().unwrap()));
+        // 3-3-4-3, but 18 HCP.
+        assert!(!shown.admits(&"AQ3.AK2.QJ84.K76".parse().unwrap()));
+        assert!(Shown::default().admits(&"T98.765.5432.432".parse().unwrap()));
+    }
+}
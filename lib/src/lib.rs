@@ -1,5 +1,26 @@
 pub mod auction;
+pub mod bidder;
+pub mod board;
 pub mod card;
+pub mod cardset;
+pub mod dds;
+pub mod deal;
 pub mod deck;
 pub mod error;
+pub mod evaluation;
+pub mod generator;
 pub mod hand;
+pub mod inference;
+pub mod lin;
+pub mod movement;
+pub mod par;
+pub mod pbn;
+pub mod play;
+pub mod player;
+pub mod rubber;
+pub mod scoring;
+pub mod script;
+pub mod session;
+pub mod shape;
+pub mod simulation;
+pub mod system;
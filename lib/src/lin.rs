@@ -0,0 +1,401 @@
+//! Reading and writing of Bridge Base Online's LIN hand records.
+
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
+
+use strum::IntoEnumIterator;
+
+use crate::{
+    auction::{Auction, AuctionBid, BiddingSuit, Seat},
+    board::Vulnerability,
+    card::{Card, Suit},
+    cardset::CardSet,
+    error::{ParseError, PlayError},
+    hand::Hand,
+    play::Play,
+};
+
+/// A single hand record, as recorded by BBO.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Record {
+    /// Player names, indexed by seat.
+    pub players: Option<[String; 4]>,
+    pub board: Option<u32>,
+    pub dealer: Seat,
+    pub vulnerability: Option<Vulnerability>,
+    /// The four hands, indexed by seat.
+    pub hands: [Hand; 4],
+    pub auction: Auction,
+    /// Explanations of alerted calls, keyed by the call's position in the auction.
+    pub alerts: BTreeMap<usize, String>,
+    /// The cards played, in order of play.
+    pub play: Vec<Card>,
+    /// The total number of tricks claimed by declarer, if the play ended in a claim.
+    pub claim: Option<u8>,
+}
+
+impl Record {
+    /// Replays the recorded cards from the start of the play. Returns `None` if the
+    /// auction didn't end in a contract.
+    pub fn replay(&self) -> Option<Result<Play, PlayError>> {
+        let mut play = Play::new(self.auction.contract()?, self.hands.clone());
+        for card in &self.play {
+            if let Err(err) = play.play_card(*card) {
+                return Some(Err(err));
+            }
+        }
+        Some(Ok(play))
+    }
+}
+
+impl FromStr for Record {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut tokens = s.split('|').map(|token| token.trim_matches(['\r', '\n']));
+
+        let (mut players, mut board, mut dealer, mut vulnerability, mut hands) =
+            (None, None, None, None, None);
+        let mut calls = vec![];
+        let mut alerts = BTreeMap::new();
+        let (mut play, mut claim) = (vec![], None);
+
+        while let Some(key) = tokens.next() {
+            if key.trim().is_empty() {
+                continue;
+            }
+            let value = tokens.next().ok_or(ParseError::TagNotValid)?;
+
+            match key.trim().to_lowercase().as_str() {
+                "pn" => players = Some(parse_players(value)?),
+                "ah" => {
+                    board = value
+                        .trim_start_matches(|c: char| !c.is_ascii_digit())
+                        .parse()
+                        .ok()
+                        .filter(|board| *board > 0)
+                }
+                "sv" => vulnerability = Some(parse_vulnerability(value)?),
+                "md" => {
+                    let (seat, deal) = parse_deal(value)?;
+                    dealer = Some(seat);
+                    hands = Some(deal);
+                }
+                "mb" => {
+                    let (call, alerted) = match value.strip_suffix('!') {
+                        Some(call) => (call, true),
+                        None => (value, false),
+                    };
+                    if alerted {
+                        alerts.insert(calls.len(), String::new());
+                    }
+                    calls.push(parse_call(call)?);
+                }
+                "an" => {
+                    let idx = calls.len().checked_sub(1).ok_or(ParseError::TagNotValid)?;
+                    alerts.insert(idx, value.to_string());
+                }
+                "pc" => play.push(parse_card(value)?),
+                "mc" => claim = Some(value.parse().map_err(|_| ParseError::ResultNotValid)?),
+                _ => {}
+            }
+        }
+
+        let dealer = dealer.ok_or(ParseError::DealNotValid)?;
+        let mut auction = Auction::new(dealer);
+        for call in calls {
+            auction
+                .enter_bid(call)
+                .map_err(|_| ParseError::CallNotLegal)?;
+        }
+
+        Ok(Self {
+            players,
+            board,
+            dealer,
+            vulnerability,
+            hands: hands.ok_or(ParseError::DealNotValid)?,
+            auction,
+            alerts,
+            play,
+            claim,
+        })
+    }
+}
+
+impl Display for Record {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut pairs = vec![];
+
+        if let Some(players) = &self.players {
+            let names: Vec<&str> = LIN_SEATS
+                .iter()
+                .map(|seat| players[*seat as usize].as_str())
+                .collect();
+            pairs.push(("pn", names.join(",")));
+        }
+        pairs.push(("st", String::new()));
+        pairs.push(("md", write_deal(self.dealer, &self.hands)));
+        pairs.push(("rh", String::new()));
+        if let Some(board) = self.board {
+            pairs.push(("ah", format!("Board {}", board)));
+        }
+        if let Some(vulnerability) = self.vulnerability {
+            pairs.push(("sv", write_vulnerability(vulnerability)));
+        }
+        for (idx, call) in self.auction.sequence().iter().enumerate() {
+            match self.alerts.get(&idx) {
+                Some(explanation) => {
+                    pairs.push(("mb", format!("{}!", write_call(call))));
+                    if !explanation.is_empty() {
+                        pairs.push(("an", explanation.clone()));
+                    }
+                }
+                None => pairs.push(("mb", write_call(call))),
+            }
+        }
+        for card in &self.play {
+            pairs.push(("pc", write_card(card)));
+        }
+        if let Some(claim) = self.claim {
+            pairs.push(("mc", claim.to_string()));
+        }
+
+        for (key, value) in pairs {
+            f.write_str(&format!("{}|{}|", key, value))?;
+        }
+        Ok(())
+    }
+}
+
+/// The order in which LIN lists seats, both for player names and hands.
+const LIN_SEATS: [Seat; 4] = [Seat::South, Seat::West, Seat::North, Seat::East];
+
+fn parse_players(s: &str) -> Result<[String; 4], ParseError> {
+    let names: Vec<&str> = s.split(',').collect();
+    if names.len() != 4 {
+        return Err(ParseError::TagNotValid);
+    }
+
+    let mut players: [String; 4] = Default::default();
+    for (seat, name) in LIN_SEATS.iter().zip(names) {
+        players[*seat as usize] = name.to_string();
+    }
+    Ok(players)
+}
+
+fn parse_vulnerability(s: &str) -> Result<Vulnerability, ParseError> {
+    match s.to_lowercase().as_str() {
+        "o" | "0" | "-" => Ok(Vulnerability::None),
+        "n" => Ok(Vulnerability::NorthSouth),
+        "e" => Ok(Vulnerability::EastWest),
+        "b" => Ok(Vulnerability::Both),
+        _ => Err(ParseError::VulnerabilityNotValid),
+    }
+}
+
+fn write_vulnerability(vulnerability: Vulnerability) -> String {
+    match vulnerability {
+        Vulnerability::None => "o",
+        Vulnerability::NorthSouth => "n",
+        Vulnerability::EastWest => "e",
+        Vulnerability::Both => "b",
+    }
+    .to_string()
+}
+
+fn parse_card(s: &str) -> Result<Card, ParseError> {
+    let mut chars = s.chars();
+    let suit = chars.next().ok_or(ParseError::TooShort)?;
+    let rank = chars.next().ok_or(ParseError::TooShort)?;
+    if chars.next().is_some() {
+        Err(ParseError::TooLong)?;
+    }
+
+    Ok(Card {
+        suit: suit.to_ascii_uppercase().try_into()?,
+        rank: rank.to_ascii_uppercase().try_into()?,
+    })
+}
+
+fn write_card(card: &Card) -> String {
+    format!("{}{}", char::from(card.suit), char::from(card.rank))
+}
+
+fn parse_call(s: &str) -> Result<AuctionBid, ParseError> {
+    match s.to_lowercase().as_str() {
+        "p" => Ok(AuctionBid::Pass),
+        "d" => Ok(AuctionBid::Double),
+        "r" => Ok(AuctionBid::Redouble),
+        call => match call.strip_suffix('n') {
+            Some(level) => format!("{}nt", level).parse(),
+            None => call.parse(),
+        },
+    }
+}
+
+fn write_call(call: &AuctionBid) -> String {
+    match call {
+        AuctionBid::Bid(bid) => match bid.suit {
+            BiddingSuit::Suit(suit) => format!("{}{}", bid.level, char::from(suit)),
+            BiddingSuit::NoTrumps => format!("{}N", bid.level),
+        },
+        AuctionBid::Pass => "p".to_string(),
+        AuctionBid::Double => "d".to_string(),
+        AuctionBid::Redouble => "r".to_string(),
+    }
+}
+
+/// Parses a hand in LIN's notation, in which each suit's letter is followed by its ranks,
+/// as in `SAK2HQJ3DT987C654`.
+fn parse_hand(s: &str) -> Result<Hand, ParseError> {
+    if s.contains('.') {
+        return Err(ParseError::DealNotValid);
+    }
+
+    s.parse().map_err(|err| match err {
+        ParseError::CardDuplicated | ParseError::HandNotValid => ParseError::DealNotValid,
+        err => err,
+    })
+}
+
+fn write_hand(hand: &Hand) -> String {
+    Suit::iter()
+        .map(|suit| {
+            let ranks: String = hand
+                .suit(suit)
+                .iter()
+                .map(|card| char::from(card.rank))
+                .collect();
+            format!("{}{}", char::from(suit), ranks)
+        })
+        .collect()
+}
+
+/// Parses the `md` value: the dealer's number followed by the hands, starting with South.
+/// The last hand may be left out, in which case it holds the remaining cards.
+fn parse_deal(s: &str) -> Result<(Seat, [Hand; 4]), ParseError> {
+    let mut chars = s.chars();
+    let dealer = match chars.next() {
+        Some('1') => Seat::South,
+        Some('2') => Seat::West,
+        Some('3') => Seat::North,
+        Some('4') => Seat::East,
+        _ => return Err(ParseError::SeatNotValid),
+    };
+
+    let hands: Vec<&str> = chars.as_str().split(',').collect();
+    if !matches!(hands.len(), 3 | 4) {
+        return Err(ParseError::DealNotValid);
+    }
+
+    let mut deal: [Hand; 4] = std::array::from_fn(|_| Hand::from(vec![]));
+    for (seat, hand) in LIN_SEATS.iter().zip(hands) {
+        deal[*seat as usize] = parse_hand(hand)?;
+    }
+
+    let held = deal
+        .iter()
+        .fold(CardSet::EMPTY, |held, hand| held | hand.cards());
+    if deal[Seat::East as usize].is_empty() {
+        deal[Seat::East as usize] = Hand::from(!held);
+    }
+
+    let mut seen = CardSet::EMPTY;
+    for card in deal.iter().flat_map(Hand::iter) {
+        if !seen.insert(card) {
+            return Err(ParseError::DealNotValid);
+        }
+    }
+    if deal.iter().any(|hand| hand.len() != 13) {
+        return Err(ParseError::DealNotValid);
+    }
+
+    Ok((dealer, deal))
+}
+
+fn write_deal(dealer: Seat, hands: &[Hand; 4]) -> String {
+    let dealer = match dealer {
+        Seat::South => '1',
+        Seat::West => '2',
+        Seat::North => '3',
+        Seat::East => '4',
+    };
+    let hands: Vec<String> = LIN_SEATS
+        .iter()
+        .map(|seat| write_hand(&hands[*seat as usize]))
+        .collect();
+    format!("{}{}", dealer, hands.join(","))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::auction::Side;
+
+    const BOARD_ONE: &str = "pn|Alice,Bob,Carol,Dave|st||\
+        md|3S876HT9876D8765CJ,S543H32D432CT9543,SAKQ2HJ54DT9C8762,|rh||ah|Board 1|sv|o|\
+        mb|p|mb|2C!|an|strong, artificial|mb|p|mb|2D|mb|p|mb|3N|mb|p|mb|p|mb|p|\
+        pc|S8|pc|S5|pc|SA|pc|S9|mc|10|";
+
+    #[test]
+    fn parse_record() {
+        let record: Record = BOARD_ONE.parse().unwrap();
+
+        assert_eq!(Seat::North, record.dealer);
+        assert_eq!(Some(1), record.board);
+        assert_eq!(Some(Vulnerability::None), record.vulnerability);
+        assert_eq!(
+            Some("Carol"),
+            record
+                .players
+                .as_ref()
+                .map(|players| players[Seat::North as usize].as_str())
+        );
+        assert_eq!(29, record.hands[Seat::East as usize].hcp());
+        assert_eq!(Some("E:3NT".parse().unwrap()), record.auction.contract());
+        assert_eq!(
+            BTreeMap::from([(1, "strong, artificial".to_string())]),
+            record.alerts
+        );
+        assert_eq!(Some(10), record.claim);
+
+        let play = record.replay().unwrap().unwrap();
+        assert_eq!(1, play.tricks().len());
+        assert_eq!(1, play.tricks_won(Side::NorthSouth));
+        assert_eq!(Seat::North, play.turn());
+    }
+
+    #[test]
+    fn round_trip() {
+        let record: Record = BOARD_ONE.parse().unwrap();
+        let written = record.to_string();
+
+        assert!(written.starts_with("pn|Alice,Bob,Carol,Dave|st||md|3S876HT9876D8765CJ,"));
+        assert!(written.contains("|mb|2C!|an|strong, artificial|mb|p|"));
+        assert!(written.ends_with("|pc|S9|mc|10|"));
+        assert_eq!(record, written.parse().unwrap());
+    }
+
+    #[test]
+    fn invalid_records() {
+        assert_eq!(Err(ParseError::DealNotValid), "mb|p|".parse::<Record>());
+        assert_eq!(
+            Err(ParseError::DealNotValid),
+            "md|3SAKQ,S2,S3,|".parse::<Record>()
+        );
+        assert_eq!(
+            Err(ParseError::CallNotLegal),
+            format!("{}mb|1C|", BOARD_ONE).parse::<Record>()
+        );
+
+        let revoke = BOARD_ONE.replace("pc|S5|", "pc|C3|");
+        assert_eq!(
+            Some(Err(PlayError::MustFollowSuit)),
+            revoke
+                .parse::<Record>()
+                .unwrap()
+                .replay()
+                .map(|play| play.map(|_| ()))
+        );
+    }
+}
@@ -0,0 +1,614 @@
+//! Movements for duplicate pairs and teams events: which pairs meet at which table in
+//! each round, and which boards they play.
+
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt::Display,
+    ops::RangeInclusive,
+};
+
+use crate::{auction::Side, error::MovementError};
+
+/// The largest field a Howell can be generated for.
+const MAX_HOWELL_PAIRS: u32 = 22;
+
+/// Two pairs meeting at a table for a round, or, in a teams match, the home team and
+/// the away team.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sitting {
+    pub table: u32,
+    pub north_south: u32,
+    pub east_west: u32,
+    /// The numbers of the boards played, as given by [`crate::board::Board::new`].
+    pub boards: RangeInclusive<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Round {
+    pub number: u32,
+    pub tables: Vec<Sitting>,
+    /// The pairs or teams which have a bye this round.
+    pub sitting_out: Vec<u32>,
+}
+
+/// The rounds of a session, in which no pair is seated twice in a round nor plays a
+/// board twice.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Movement {
+    rounds: Vec<Round>,
+}
+
+/// Returns the boards of a set, counting sets from zero.
+fn board_set(set: u32, boards_per_round: u32) -> RangeInclusive<u32> {
+    set * boards_per_round + 1..=(set + 1) * boards_per_round
+}
+
+impl Movement {
+    /// Returns the movement with the given rounds, or an error if they seat a pair twice
+    /// in a round or give a pair a board twice.
+    pub fn new(rounds: Vec<Round>) -> Result<Self, MovementError> {
+        let movement = Self { rounds };
+        movement.validate()?;
+        Ok(movement)
+    }
+
+    pub fn rounds(&self) -> &[Round] {
+        &self.rounds
+    }
+
+    /// Returns a Mitchell for the given number of tables, played over as many rounds.
+    /// North-South pairs are numbered after their tables and stay there; East-West pairs
+    /// are numbered after them, so that the pair starting East-West at table 1 is pair
+    /// `tables + 1`. East-West pairs move up a table each round and boards move down.
+    ///
+    /// With an even number of tables, the boards move through a relay, shared by tables
+    /// 1 and `tables`, and a bye stand between tables `tables / 2` and `tables / 2 + 1`,
+    /// so that no pair meets the same boards twice.
+    pub fn mitchell(tables: u32, boards_per_round: u32) -> Result<Self, MovementError> {
+        if tables < 3 {
+            return Err(MovementError::TablesNotValid);
+        }
+        if boards_per_round == 0 {
+            return Err(MovementError::BoardsNotValid);
+        }
+
+        // With a relay and bye, the stations the boards pass through in order: `None` for
+        // the bye, and the relay as table 1 (table `tables` playing the same set).
+        let half = tables / 2;
+        let stations: Vec<Option<u32>> = std::iter::once(Some(1))
+            .chain((half + 1..tables).rev().map(Some))
+            .chain(std::iter::once(None))
+            .chain((2..=half).rev().map(Some))
+            .collect();
+
+        let rounds = (0..tables)
+            .map(|round| {
+                let set = |table: u32| match tables % 2 {
+                    1 => (table - 1 + round) % tables,
+                    _ => {
+                        let table = if table == tables { 1 } else { table };
+                        let station = stations.iter().position(|s| *s == Some(table)).unwrap();
+                        (station as u32 + tables - round % tables) % tables
+                    }
+                };
+                Round {
+                    number: round + 1,
+                    tables: (1..=tables)
+                        .map(|table| Sitting {
+                            table,
+                            north_south: table,
+                            east_west: tables + (table + tables - 1 - round) % tables + 1,
+                            boards: board_set(set(table), boards_per_round),
+                        })
+                        .collect(),
+                    sitting_out: vec![],
+                }
+            })
+            .collect();
+        Self::new(rounds)
+    }
+
+    /// Returns a complete Howell for the given number of pairs, in which every pair meets
+    /// every other. With an odd number of pairs, the pair which would have met the
+    /// missing pair sits out.
+    ///
+    /// From eight pairs upward, each table plays a different set of boards each round.
+    /// With fewer, that is impossible, and every table plays the same set in a round, so
+    /// the boards need to be duplicated or shared between the tables.
+    pub fn howell(pairs: u32, boards_per_round: u32) -> Result<Self, MovementError> {
+        if !(4..=MAX_HOWELL_PAIRS).contains(&pairs) {
+            return Err(MovementError::PairsNotValid);
+        }
+        if boards_per_round == 0 {
+            return Err(MovementError::BoardsNotValid);
+        }
+
+        // Pairs other than the stationary pair are numbered modulo `n`, and the
+        // stationary pair meets pair `r` in round `r`. The remaining meetings of a round
+        // are a starter, a set of pairs covering every other number and every
+        // difference once, shifted by the round. Below seven there's no starter whose
+        // tables can all play different sets, so every table plays the round's set.
+        let n = pairs + pairs % 2 - 1;
+        let stationary = n;
+        let starter = if n >= 7 {
+            starter_adder(n).ok_or(MovementError::PairsNotValid)?
+        } else {
+            (1..=n / 2).map(|i| (i, n - i, 0)).collect()
+        };
+
+        let rounds = (0..n)
+            .map(|round| {
+                let mut sittings = vec![(stationary, round, round)];
+                for (x, y, a) in &starter {
+                    sittings.push(((x + round) % n, (y + round) % n, (round + n - a) % n));
+                }
+
+                let mut tables = vec![];
+                let mut sitting_out = vec![];
+                for (north_south, east_west, set) in sittings {
+                    let north_south = north_south + 1;
+                    let east_west = east_west + 1;
+                    if north_south > pairs {
+                        sitting_out.push(east_west);
+                    } else {
+                        tables.push(Sitting {
+                            table: tables.len() as u32 + 1,
+                            north_south,
+                            east_west,
+                            boards: board_set(set, boards_per_round),
+                        });
+                    }
+                }
+                Round {
+                    number: round + 1,
+                    tables,
+                    sitting_out,
+                }
+            })
+            .collect();
+        Self::new(rounds)
+    }
+
+    /// Returns an empty Swiss teams event, to which rounds are added by
+    /// [`Self::swiss_round`] as the scores come in.
+    pub fn swiss() -> Self {
+        Self::default()
+    }
+
+    /// Pairs the teams for the next round of a Swiss, given each team's number and
+    /// score so far, and adds the round to the movement.
+    ///
+    /// Teams are paired from the top of the standings down, each with the highest placed
+    /// team it hasn't met, and the home team is listed as North-South: it sits
+    /// North-South in the open room and East-West in the closed room. With an odd number
+    /// of teams, the lowest placed team which hasn't had a bye sits out.
+    pub fn swiss_round(
+        &mut self,
+        standings: &[(u32, f64)],
+        boards_per_round: u32,
+    ) -> Result<&Round, MovementError> {
+        if boards_per_round == 0 {
+            return Err(MovementError::BoardsNotValid);
+        }
+
+        let mut teams: Vec<_> = standings.to_vec();
+        teams.sort_by(|lhs, rhs| rhs.1.total_cmp(&lhs.1).then(lhs.0.cmp(&rhs.0)));
+        let mut teams: Vec<u32> = teams.into_iter().map(|(team, _)| team).collect();
+
+        let mut sitting_out = vec![];
+        if teams.len() % 2 == 1 {
+            let had_bye = |team: &u32| {
+                self.rounds
+                    .iter()
+                    .any(|round| round.sitting_out.contains(team))
+            };
+            let idx = teams
+                .iter()
+                .rposition(|team| !had_bye(team))
+                .unwrap_or(teams.len() - 1);
+            sitting_out.push(teams.remove(idx));
+        }
+
+        let met: HashSet<(u32, u32)> = self
+            .rounds
+            .iter()
+            .flat_map(|round| &round.tables)
+            .flat_map(|sitting| {
+                [
+                    (sitting.north_south, sitting.east_west),
+                    (sitting.east_west, sitting.north_south),
+                ]
+            })
+            .collect();
+        let mut matches = vec![];
+        if !pair_teams(&mut teams, &met, &mut matches) {
+            return Err(MovementError::PairingImpossible);
+        }
+
+        let number = self.rounds.len() as u32 + 1;
+        let round = Round {
+            number,
+            tables: matches
+                .into_iter()
+                .enumerate()
+                .map(|(idx, (home, away))| Sitting {
+                    table: idx as u32 + 1,
+                    north_south: home,
+                    east_west: away,
+                    boards: board_set(number - 1, boards_per_round),
+                })
+                .collect(),
+            sitting_out,
+        };
+        let mut rounds = self.rounds.clone();
+        rounds.push(round);
+        *self = Self::new(rounds)?;
+        Ok(self.rounds.last().unwrap())
+    }
+
+    /// Swaps the North-South and East-West pairs in the given round, counted from 1, so
+    /// that a two-winner Mitchell gives one winner.
+    pub fn arrow_switch(mut self, round: u32) -> Self {
+        for sitting in self
+            .rounds
+            .iter_mut()
+            .filter(|other| other.number == round)
+            .flat_map(|round| &mut round.tables)
+        {
+            std::mem::swap(&mut sitting.north_south, &mut sitting.east_west);
+        }
+        self
+    }
+
+    /// Returns the direction each pair sits, or `None` if any pair sits both ways round,
+    /// as in a Howell or after an arrow switch.
+    pub fn directions(&self) -> Option<BTreeMap<u32, Side>> {
+        let mut directions = BTreeMap::new();
+        for sitting in self.rounds.iter().flat_map(|round| &round.tables) {
+            for (pair, side) in [
+                (sitting.north_south, Side::NorthSouth),
+                (sitting.east_west, Side::EastWest),
+            ] {
+                if *directions.entry(pair).or_insert(side) != side {
+                    return None;
+                }
+            }
+        }
+        Some(directions)
+    }
+
+    /// Checks that no pair is seated twice in a round and that no pair plays a board
+    /// twice.
+    pub fn validate(&self) -> Result<(), MovementError> {
+        let mut played = HashSet::new();
+        for round in &self.rounds {
+            let mut seated = HashSet::new();
+            let pairs = round
+                .tables
+                .iter()
+                .flat_map(|sitting| [sitting.north_south, sitting.east_west])
+                .chain(round.sitting_out.iter().copied());
+            for pair in pairs {
+                if !seated.insert(pair) {
+                    return Err(MovementError::PairSeatedTwice);
+                }
+            }
+
+            for sitting in &round.tables {
+                for board in sitting.boards.clone() {
+                    if !played.insert((sitting.north_south, board))
+                        || !played.insert((sitting.east_west, board))
+                    {
+                        return Err(MovementError::BoardPlayedTwice);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Pairs the teams in order, each with the first later team it hasn't met, backtracking
+/// when the teams left can't all be paired.
+fn pair_teams(
+    teams: &mut Vec<u32>,
+    met: &HashSet<(u32, u32)>,
+    matches: &mut Vec<(u32, u32)>,
+) -> bool {
+    if teams.is_empty() {
+        return true;
+    }
+    let home = teams.remove(0);
+    for idx in 0..teams.len() {
+        let away = teams[idx];
+        if met.contains(&(home, away)) {
+            continue;
+        }
+        teams.remove(idx);
+        matches.push((home, away));
+        if pair_teams(teams, met, matches) {
+            return true;
+        }
+        matches.pop();
+        teams.insert(idx, away);
+    }
+    teams.insert(0, home);
+    false
+}
+
+/// Searches for a starter and adder over the integers modulo `n`, as triples `(x, y, a)`:
+/// pairs `(x, y)` which between them cover every nonzero number once and every
+/// difference `±(y - x)` once, with distinct nonzero shifts `a` such that the shifted
+/// pairs `(x + a, y + a)` also cover every nonzero number once. Pair `(x, y)` meets in round `r` as
+/// `(x + r, y + r)` and plays set `r - a`, so that every pair meets every other once and
+/// plays every set once.
+fn starter_adder(n: u32) -> Option<Vec<(u32, u32, u32)>> {
+    struct Search {
+        n: u32,
+        used: Vec<bool>,
+        differences: Vec<bool>,
+        adders: Vec<bool>,
+        covered: Vec<bool>,
+        starter: Vec<(u32, u32, u32)>,
+    }
+
+    impl Search {
+        fn search(&mut self) -> bool {
+            let n = self.n;
+            let Some(x) = (1..n).find(|x| !self.used[*x as usize]) else {
+                return true;
+            };
+            for y in x + 1..n {
+                let difference = (y - x).min(n - y + x) as usize;
+                if self.used[y as usize] || self.differences[difference] {
+                    continue;
+                }
+                self.used[x as usize] = true;
+                self.used[y as usize] = true;
+                self.differences[difference] = true;
+                for a in 1..n {
+                    let (u, v) = (((x + a) % n) as usize, ((y + a) % n) as usize);
+                    if self.adders[a as usize]
+                        || u == 0
+                        || v == 0
+                        || self.covered[u]
+                        || self.covered[v]
+                    {
+                        continue;
+                    }
+                    self.adders[a as usize] = true;
+                    self.covered[u] = true;
+                    self.covered[v] = true;
+                    self.starter.push((x, y, a));
+                    if self.search() {
+                        return true;
+                    }
+                    self.starter.pop();
+                    self.adders[a as usize] = false;
+                    self.covered[u] = false;
+                    self.covered[v] = false;
+                }
+                self.used[x as usize] = false;
+                self.used[y as usize] = false;
+                self.differences[difference] = false;
+            }
+            false
+        }
+    }
+
+    let size = n as usize;
+    let mut search = Search {
+        n,
+        used: vec![false; size],
+        differences: vec![false; size],
+        adders: vec![false; size],
+        covered: vec![false; size],
+        starter: vec![],
+    };
+    match search.search() {
+        true => Some(search.starter),
+        false => None,
+    }
+}
+
+impl Display for Sitting {
+    /// Writes the sitting as "Table 1: NS 1 v EW 8, boards 1-3".
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Table {}: NS {} v EW {}, ",
+            self.table, self.north_south, self.east_west
+        )?;
+        match self.boards.start() == self.boards.end() {
+            true => write!(f, "board {}", self.boards.start()),
+            false => write!(f, "boards {}-{}", self.boards.start(), self.boards.end()),
+        }
+    }
+}
+
+impl Display for Round {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Round {}", self.number)?;
+        for sitting in &self.tables {
+            write!(f, "\n  {}", sitting)?;
+        }
+        for pair in &self.sitting_out {
+            write!(f, "\n  Pair {} sits out", pair)?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for Movement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (idx, round) in self.rounds.iter().enumerate() {
+            if idx > 0 {
+                f.write_str("\n")?;
+            }
+            write!(f, "{}", round)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Returns the opponents each pair meets over the movement.
+    fn opponents(movement: &Movement) -> Vec<(u32, u32)> {
+        let mut opponents: Vec<_> = movement
+            .rounds()
+            .iter()
+            .flat_map(|round| &round.tables)
+            .map(|sitting| {
+                let (ns, ew) = (sitting.north_south, sitting.east_west);
+                (ns.min(ew), ns.max(ew))
+            })
+            .collect();
+        opponents.sort();
+        opponents
+    }
+
+    #[test]
+    fn mitchells() {
+        for tables in 3..=15 {
+            let movement = Movement::mitchell(tables, 2).unwrap();
+            assert_eq!(tables as usize, movement.rounds().len());
+            // Every North-South pair meets every East-West pair once.
+            let expected: Vec<_> = (1..=tables)
+                .flat_map(|ns| (tables + 1..=2 * tables).map(move |ew| (ns, ew)))
+                .collect();
+            assert_eq!(expected, opponents(&movement));
+        }
+
+        let movement = Movement::mitchell(5, 3).unwrap();
+        assert_eq!(
+            "Round 2\n  Table 1: NS 1 v EW 10, boards 4-6\n  Table 2: NS 2 v EW 6, boards 7-9",
+            movement.rounds()[1]
+                .to_string()
+                .lines()
+                .take(3)
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+
+        // Tables 1 and 4 share the relay and the bye stand sits between tables 2 and 3.
+        let movement = Movement::mitchell(4, 1).unwrap();
+        let boards: Vec<_> = movement.rounds()[0]
+            .tables
+            .iter()
+            .map(|sitting| *sitting.boards.start())
+            .collect();
+        assert_eq!(vec![1, 4, 2, 1], boards);
+
+        assert_eq!(Err(MovementError::TablesNotValid), Movement::mitchell(2, 3));
+        assert_eq!(Err(MovementError::BoardsNotValid), Movement::mitchell(5, 0));
+    }
+
+    #[test]
+    fn howells() {
+        for pairs in 4..=MAX_HOWELL_PAIRS {
+            let movement = Movement::howell(pairs, 2).unwrap();
+            let rounds = pairs + pairs % 2 - 1;
+            assert_eq!(rounds as usize, movement.rounds().len());
+            // Every pair meets every other once.
+            let expected: Vec<_> = (1..=pairs)
+                .flat_map(|lhs| (lhs + 1..=pairs).map(move |rhs| (lhs, rhs)))
+                .collect();
+            assert_eq!(expected, opponents(&movement));
+            for round in movement.rounds() {
+                assert_eq!(pairs % 2, round.sitting_out.len() as u32);
+            }
+        }
+
+        // From eight pairs, no two tables play the same boards in a round.
+        let movement = Movement::howell(8, 3).unwrap();
+        for round in movement.rounds() {
+            let sets: HashSet<_> = round.tables.iter().map(|s| s.boards.start()).collect();
+            assert_eq!(4, sets.len());
+        }
+
+        assert_eq!(Err(MovementError::PairsNotValid), Movement::howell(3, 3));
+        assert_eq!(Err(MovementError::PairsNotValid), Movement::howell(30, 3));
+    }
+
+    #[test]
+    fn arrow_switch() {
+        let movement = Movement::mitchell(3, 2).unwrap().arrow_switch(3);
+        assert_eq!(Ok(()), movement.validate());
+        let sitting = &movement.rounds()[2].tables[0];
+        assert_eq!((5, 1), (sitting.north_south, sitting.east_west));
+        assert_eq!(1, movement.rounds()[1].tables[0].north_south);
+
+        assert_eq!(None, movement.directions());
+        let directions = Movement::mitchell(3, 2).unwrap().directions().unwrap();
+        assert_eq!(Some(&Side::NorthSouth), directions.get(&3));
+        assert_eq!(Some(&Side::EastWest), directions.get(&4));
+    }
+
+    #[test]
+    fn swiss() {
+        let mut movement = Movement::swiss();
+        let round = movement
+            .swiss_round(&[(1, 0.0), (2, 0.0), (3, 0.0), (4, 0.0), (5, 0.0)], 8)
+            .unwrap();
+        assert_eq!(vec![5], round.sitting_out);
+        assert_eq!(
+            "Round 1\n  Table 1: NS 1 v EW 2, boards 1-8\n  Table 2: NS 3 v EW 4, boards 1-8\n  \
+             Pair 5 sits out",
+            round.to_string()
+        );
+
+        // Teams 1 and 2 lead, but have met, so play the next teams down.
+        let round = movement
+            .swiss_round(&[(1, 15.0), (2, 12.0), (3, 5.0), (4, 8.0), (5, 10.0)], 8)
+            .unwrap();
+        let matches: Vec<_> = round
+            .tables
+            .iter()
+            .map(|sitting| (sitting.north_south, sitting.east_west))
+            .collect();
+        assert_eq!(vec![(1, 5), (2, 4)], matches);
+        assert_eq!(vec![3], round.sitting_out);
+        assert_eq!(9..=16, round.tables[0].boards);
+
+        let mut movement = Movement::swiss();
+        movement.swiss_round(&[(1, 0.0), (2, 0.0)], 8).unwrap();
+        assert_eq!(
+            Err(MovementError::PairingImpossible),
+            movement.swiss_round(&[(1, 0.0), (2, 0.0)], 8).map(|_| ())
+        );
+        assert_eq!(1, movement.rounds().len());
+    }
+
+    #[test]
+    fn validation() {
+        let sitting = |table, north_south, east_west, boards| Sitting {
+            table,
+            north_south,
+            east_west,
+            boards,
+        };
+        let round = |number, tables| Round {
+            number,
+            tables,
+            sitting_out: vec![],
+        };
+
+        assert_eq!(
+            Err(MovementError::PairSeatedTwice),
+            Movement::new(vec![round(
+                1,
+                vec![sitting(1, 1, 2, 1..=2), sitting(2, 3, 1, 3..=4)]
+            )])
+        );
+        assert_eq!(
+            Err(MovementError::BoardPlayedTwice),
+            Movement::new(vec![
+                round(1, vec![sitting(1, 1, 2, 1..=2)]),
+                round(2, vec![sitting(1, 1, 3, 2..=3)]),
+            ])
+        );
+    }
+}
@@ -0,0 +1,207 @@
+//! Par: the result of a deal when both sides bid to their best double-dummy contract, and
+//! neither side can gain by bidding on or by sacrificing.
+
+use std::fmt::Display;
+
+use crate::{
+    auction::{Contract, ContractBid, Seat, Side, Status},
+    board::Vulnerability,
+    dds::{Table, STRAINS},
+};
+
+/// The par result of a deal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Par {
+    /// The contracts which score par, at the lowest level in each strain for each
+    /// declarer. Contracts which go down are doubled. Empty if par is to pass the deal out.
+    pub contracts: Vec<Contract>,
+    /// The par score, from North-South's point of view.
+    pub score: i32,
+}
+
+impl Display for Par {
+    /// Writes the par contracts followed by the score from the point of view of the side
+    /// declaring the first of them, e.g. "4♠X by E -500".
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Some(first) = self.contracts.first() else {
+            return f.write_str("Passed out");
+        };
+        let contracts: Vec<_> = self.contracts.iter().map(Contract::to_string).collect();
+        write!(
+            f,
+            "{} {:+}",
+            contracts.join(", "),
+            self.score * sign(first.declarer.side())
+        )
+    }
+}
+
+/// Returns the factor which turns a score from North-South's point of view into one from
+/// the given side's point of view, and back.
+fn sign(side: Side) -> i32 {
+    match side {
+        Side::NorthSouth => 1,
+        Side::EastWest => -1,
+    }
+}
+
+/// Returns the score among `scores` (from North-South's point of view) which is best for
+/// the given side.
+fn best_for(side: Side, scores: impl IntoIterator<Item = i32>) -> i32 {
+    scores
+        .into_iter()
+        .max_by_key(|score| score * sign(side))
+        .unwrap()
+}
+
+impl Table {
+    /// Returns the contract the given seat would play in at `bid`, doubled if it goes
+    /// down, and its score from North-South's point of view.
+    fn outcome(
+        &self,
+        bid: ContractBid,
+        declarer: Seat,
+        vulnerability: Vulnerability,
+    ) -> (Contract, i32) {
+        let tricks = self.tricks(bid.suit, declarer);
+        let mut contract = Contract {
+            bid,
+            status: Status::Undoubled,
+            declarer,
+        };
+        if tricks < contract.target() {
+            contract.status = Status::Doubled;
+        }
+        let side = declarer.side();
+        let score = contract.score(vulnerability.is_vulnerable(side), tricks);
+        (contract, score * sign(side))
+    }
+
+    /// Returns the par result for the deal with the given vulnerability. The dealer's side
+    /// has the first chance to bid, which only matters when both sides can make the same
+    /// contract.
+    pub fn par(&self, vulnerability: Vulnerability, dealer: Seat) -> Par {
+        let bids: Vec<_> = (1..=7)
+            .flat_map(|level| STRAINS.map(|strain| ContractBid::new(level, strain).unwrap()))
+            .collect();
+        let sides = [Side::NorthSouth, Side::EastWest];
+        let declarers = |side: Side| match side {
+            Side::NorthSouth => [Seat::North, Seat::South],
+            Side::EastWest => [Seat::East, Seat::West],
+        };
+        let passed_out = |bid: ContractBid, side: Side| {
+            best_for(
+                side,
+                declarers(side).map(|seat| self.outcome(bid, seat, vulnerability).1),
+            )
+        };
+
+        // The result, from North-South's point of view, when a side has just made a bid and
+        // both sides bid perfectly from there, found working down from 7NT. The opponents
+        // either pass, leaving the side to play there, or bid on.
+        let mut values = vec![[0; 2]; bids.len()];
+        // The best result each side can get by bidding over the bids considered so far.
+        let mut over: [Option<i32>; 2] = [None; 2];
+        for (idx, bid) in bids.iter().enumerate().rev() {
+            for side in sides {
+                let opponents = side.opponents();
+                let passed = passed_out(*bid, side);
+                values[idx][side as usize] = best_for(
+                    opponents,
+                    [Some(passed), over[opponents as usize]]
+                        .into_iter()
+                        .flatten(),
+                );
+            }
+            for side in sides {
+                let value = values[idx][side as usize];
+                over[side as usize] = Some(best_for(
+                    side,
+                    [Some(value), over[side as usize]].into_iter().flatten(),
+                ));
+            }
+        }
+
+        let first = dealer.side();
+        let second = first.opponents();
+        let after_pass = best_for(second, [0, over[second as usize].unwrap()]);
+        let score = best_for(first, [after_pass, over[first as usize].unwrap()]);
+
+        // Contracts below one the opponents can make may tie with par, but the opponents
+        // would bid over them, so only those at or above it count.
+        let highest = |side: Side| {
+            bids.iter().rposition(|bid| {
+                declarers(side)
+                    .iter()
+                    .any(|seat| self.tricks(bid.suit, *seat) >= bid.level.get() + 6)
+            })
+        };
+        let mut contracts: Vec<Contract> = vec![];
+        for (idx, bid) in bids.iter().enumerate() {
+            for side in sides {
+                let outbid = highest(side.opponents()).is_some_and(|highest| highest > idx);
+                if values[idx][side as usize] != score || outbid {
+                    continue;
+                }
+                for seat in declarers(side) {
+                    let (contract, value) = self.outcome(*bid, seat, vulnerability);
+                    let lower = contracts
+                        .iter()
+                        .any(|other| other.bid.suit == bid.suit && other.declarer == seat);
+                    if value == score && !lower {
+                        contracts.push(contract);
+                    }
+                }
+            }
+        }
+
+        Par { contracts, score }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Returns a table in which North and South take `north_south` tricks in each strain
+    /// (from clubs up to no trumps), and East and West take the rest.
+    fn table(north_south: [u8; 5]) -> Table {
+        Table(north_south.map(|tricks| [tricks, 13 - tricks, tricks, 13 - tricks]))
+    }
+
+    #[test]
+    fn making_game() {
+        let par = table([6, 7, 6, 10, 8]).par(Vulnerability::None, Seat::North);
+        assert_eq!(420, par.score);
+        assert_eq!("4♠ by N, 4♠ by S +420", par.to_string());
+
+        let par = table([6, 7, 6, 10, 8]).par(Vulnerability::NorthSouth, Seat::East);
+        assert_eq!(620, par.score);
+    }
+
+    #[test]
+    fn sacrifice() {
+        let par = table([6, 7, 4, 10, 8]).par(Vulnerability::None, Seat::North);
+        assert_eq!(300, par.score);
+        assert_eq!("5♥X by E, 5♥X by W -300", par.to_string());
+
+        // Vulnerable, the sacrifice costs more than the game.
+        let par = table([6, 7, 4, 10, 8]).par(Vulnerability::EastWest, Seat::North);
+        assert_eq!(420, par.score);
+    }
+
+    #[test]
+    fn part_score() {
+        // Either side can make 1NT, so the dealer's side gets there first.
+        let mut both = Table([[6; 4]; 5]);
+        both.0[4] = [7; 4];
+        let par = both.par(Vulnerability::None, Seat::West);
+        assert_eq!(-90, par.score);
+        assert_eq!("1NT by E, 1NT by W +90", par.to_string());
+
+        let par = Table([[6; 4]; 5]).par(Vulnerability::None, Seat::North);
+        assert_eq!(0, par.score);
+        assert!(par.contracts.is_empty());
+        assert_eq!("Passed out", par.to_string());
+    }
+}
@@ -0,0 +1,567 @@
+//! Reading and writing of [Portable Bridge Notation](<https://www.tistis.nl/pbn/>) files.
+
+use std::{collections::HashMap, fmt::Display};
+
+use crate::{
+    auction::{Auction, AuctionBid, BiddingSuit, Contract, ContractBid, Seat, Status},
+    board::Vulnerability,
+    card::Suit,
+    cardset::CardSet,
+    error::ParseError,
+    hand::Hand,
+};
+
+/// The order in which tags are written, following the PBN export format. Tags not listed
+/// here are written after the auction.
+const TAG_ORDER: [&str; 16] = [
+    "Event",
+    "Site",
+    "Date",
+    "Board",
+    "West",
+    "North",
+    "East",
+    "South",
+    "Dealer",
+    "Vulnerable",
+    "Deal",
+    "Scoring",
+    "Declarer",
+    "Contract",
+    "Result",
+    "Auction",
+];
+
+/// A tag pair, along with the lines of any section data following it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tag {
+    pub name: String,
+    pub value: String,
+    pub section: Vec<String>,
+}
+
+impl Tag {
+    fn new(name: &str, value: String) -> Self {
+        Self {
+            name: name.to_string(),
+            value,
+            section: vec![],
+        }
+    }
+}
+
+impl Display for Tag {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let value = self.value.replace('\\', "\\\\").replace('"', "\\\"");
+        f.write_str(&format!("[{} \"{}\"]", self.name, value))?;
+        for line in &self.section {
+            f.write_str(&format!("\n{}", line))?;
+        }
+        Ok(())
+    }
+}
+
+/// A single game from a PBN file. Tags parker doesn't interpret are kept in `tags`, so
+/// that they survive a round trip.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Game {
+    pub board: Option<u32>,
+    pub dealer: Option<Seat>,
+    pub vulnerability: Option<Vulnerability>,
+    /// The four hands, indexed by seat.
+    pub deal: Option<[Hand; 4]>,
+    pub auction: Option<Auction>,
+    pub contract: Option<Contract>,
+    /// The number of tricks taken by declarer.
+    pub result: Option<u8>,
+    pub tags: Vec<Tag>,
+}
+
+impl Display for Game {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut tags = self.tags.clone();
+
+        if let Some(board) = self.board {
+            tags.push(Tag::new("Board", board.to_string()));
+        }
+        if let Some(dealer) = self.dealer {
+            tags.push(Tag::new("Dealer", char::from(dealer).to_string()));
+        }
+        if let Some(vulnerability) = self.vulnerability {
+            tags.push(Tag::new("Vulnerable", write_vulnerability(vulnerability)));
+        }
+        if let Some(deal) = &self.deal {
+            tags.push(Tag::new("Deal", write_deal(deal)));
+        }
+        if let Some(contract) = self.contract {
+            tags.push(Tag::new(
+                "Declarer",
+                char::from(contract.declarer).to_string(),
+            ));
+            tags.push(Tag::new("Contract", write_contract(&contract)));
+        }
+        if let Some(result) = self.result {
+            tags.push(Tag::new("Result", result.to_string()));
+        }
+        if let Some(auction) = &self.auction {
+            let mut tag = Tag::new("Auction", char::from(*auction.dealer()).to_string());
+            tag.section = auction
+                .sequence()
+                .chunks(4)
+                .map(|calls| calls.iter().map(write_call).collect::<Vec<_>>().join(" "))
+                .collect();
+            tags.push(tag);
+        }
+
+        tags.sort_by_key(|tag| {
+            TAG_ORDER
+                .iter()
+                .position(|name| *name == tag.name)
+                .unwrap_or(TAG_ORDER.len())
+        });
+
+        f.write_str(
+            &tags
+                .iter()
+                .map(Tag::to_string)
+                .collect::<Vec<_>>()
+                .join("\n"),
+        )
+    }
+}
+
+impl Game {
+    fn from_tags(tags: Vec<Tag>) -> Result<Self, ParseError> {
+        let mut game = Self::default();
+        let (mut declarer, mut contract) = (None, None);
+
+        for tag in tags {
+            let value = tag.value.as_str();
+            if matches!(value, "" | "?") && TAG_ORDER.contains(&tag.name.as_str()) {
+                continue;
+            }
+
+            match tag.name.as_str() {
+                "Board" => match value.parse() {
+                    Ok(board) if board > 0 => game.board = Some(board),
+                    _ => return Err(ParseError::TagNotValid),
+                },
+                "Dealer" => game.dealer = Some(value.parse()?),
+                "Vulnerable" => game.vulnerability = Some(value.parse()?),
+                "Deal" => game.deal = Some(parse_deal(value)?),
+                "Declarer" => declarer = Some(value.parse::<Seat>()?),
+                "Contract" => contract = Some(value.to_string()),
+                "Result" => {
+                    game.result = Some(value.parse().map_err(|_| ParseError::ResultNotValid)?)
+                }
+                "Auction" => game.auction = Some(parse_auction(value.parse()?, &tag.section)?),
+                _ => game.tags.push(tag),
+            }
+        }
+
+        if let Some(contract) = contract {
+            game.contract = parse_contract(&contract, declarer)?;
+        }
+
+        Ok(game)
+    }
+}
+
+/// Parses every game in a PBN file.
+pub fn parse(input: &str) -> Result<Vec<Game>, ParseError> {
+    let mut games = vec![];
+    // Tags with the value "#" take their value from the same tag in the previous game.
+    let mut previous: HashMap<String, String> = HashMap::new();
+
+    for mut tags in read_tags(input)? {
+        for tag in tags.iter_mut() {
+            if tag.value == "#" {
+                tag.value = previous.get(&tag.name).cloned().unwrap_or_default();
+            }
+        }
+        previous = tags
+            .iter()
+            .map(|tag| (tag.name.clone(), tag.value.clone()))
+            .collect();
+
+        games.push(Game::from_tags(tags)?);
+    }
+
+    Ok(games)
+}
+
+/// Writes games out as a PBN file.
+pub fn write(games: &[Game]) -> String {
+    let games = games
+        .iter()
+        .map(Game::to_string)
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    format!("% PBN 2.1\n{}\n", games)
+}
+
+/// Splits a PBN file into games, each a list of tags. Games are separated by blank lines,
+/// and comments are discarded.
+fn read_tags(input: &str) -> Result<Vec<Vec<Tag>>, ParseError> {
+    let mut games = vec![];
+    let mut tags: Vec<Tag> = vec![];
+    let mut in_comment = false;
+
+    for line in input.lines() {
+        if line.trim().is_empty() && !in_comment {
+            if !tags.is_empty() {
+                games.push(std::mem::take(&mut tags));
+            }
+            continue;
+        }
+        if line.starts_with('%') {
+            continue;
+        }
+
+        let line = strip_comments(line, &mut in_comment);
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        if line.starts_with('[') {
+            tags.push(parse_tag(line)?);
+        } else {
+            tags.last_mut()
+                .ok_or(ParseError::TagNotValid)?
+                .section
+                .push(line.to_string());
+        }
+    }
+    if !tags.is_empty() {
+        games.push(tags);
+    }
+
+    Ok(games)
+}
+
+/// Removes `{...}` comments (which may span lines) and `;` comments from a line, leaving
+/// quoted tag values untouched.
+fn strip_comments(line: &str, in_comment: &mut bool) -> String {
+    let mut stripped = String::new();
+    let (mut in_quote, mut escaped) = (false, false);
+
+    for c in line.chars() {
+        if *in_comment {
+            *in_comment = c != '}';
+            continue;
+        }
+        match c {
+            '{' if !in_quote => *in_comment = true,
+            ';' if !in_quote => break,
+            '"' if !escaped => in_quote = !in_quote,
+            _ => {}
+        }
+        escaped = in_quote && c == '\\' && !escaped;
+        if !*in_comment {
+            stripped.push(c);
+        }
+    }
+
+    stripped
+}
+
+fn parse_tag(line: &str) -> Result<Tag, ParseError> {
+    let inner = line
+        .strip_prefix('[')
+        .and_then(|line| line.strip_suffix(']'))
+        .ok_or(ParseError::TagNotValid)?;
+    let (name, quoted) = inner
+        .trim()
+        .split_once(char::is_whitespace)
+        .ok_or(ParseError::TagNotValid)?;
+    let quoted = quoted
+        .trim()
+        .strip_prefix('"')
+        .and_then(|value| value.strip_suffix('"'))
+        .ok_or(ParseError::TagNotValid)?;
+
+    let mut value = String::new();
+    let mut chars = quoted.chars();
+    while let Some(c) = chars.next() {
+        value.push(match c {
+            '\\' => chars.next().ok_or(ParseError::TagNotValid)?,
+            _ => c,
+        });
+    }
+
+    Ok(Tag::new(name, value))
+}
+
+fn parse_hand(s: &str) -> Result<Hand, ParseError> {
+    if s == "-" {
+        return Ok(Hand::from(vec![]));
+    }
+    if s.split('.').count() != 4 {
+        return Err(ParseError::DealNotValid);
+    }
+
+    s.parse().map_err(|err| match err {
+        ParseError::CardDuplicated | ParseError::HandNotValid => ParseError::DealNotValid,
+        err => err,
+    })
+}
+
+fn write_hand(hand: &Hand) -> String {
+    if hand.is_empty() {
+        return "-".to_string();
+    }
+
+    [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs]
+        .iter()
+        .map(|suit| {
+            hand.suit(*suit)
+                .iter()
+                .map(|card| char::from(card.rank))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+fn parse_deal(s: &str) -> Result<[Hand; 4], ParseError> {
+    let (first, hands) = s.split_once(':').ok_or(ParseError::DealNotValid)?;
+    let first: Seat = first.parse()?;
+    let hands: Vec<&str> = hands.split_whitespace().collect();
+    if hands.len() != 4 {
+        return Err(ParseError::DealNotValid);
+    }
+
+    let mut deal: [Hand; 4] = std::array::from_fn(|_| Hand::from(vec![]));
+    for (idx, hand) in hands.into_iter().enumerate() {
+        deal[(first + idx) as usize] = parse_hand(hand)?;
+    }
+
+    let mut seen = CardSet::EMPTY;
+    for hand in &deal {
+        if !matches!(hand.len(), 0 | 13) {
+            return Err(ParseError::DealNotValid);
+        }
+        for card in hand.iter() {
+            if !seen.insert(card) {
+                return Err(ParseError::DealNotValid);
+            }
+        }
+    }
+
+    Ok(deal)
+}
+
+fn write_deal(deal: &[Hand; 4]) -> String {
+    format!(
+        "N:{}",
+        deal.iter().map(write_hand).collect::<Vec<_>>().join(" ")
+    )
+}
+
+fn write_vulnerability(vulnerability: Vulnerability) -> String {
+    match vulnerability {
+        Vulnerability::None => "None",
+        Vulnerability::NorthSouth => "NS",
+        Vulnerability::EastWest => "EW",
+        Vulnerability::Both => "All",
+    }
+    .to_string()
+}
+
+fn parse_contract(s: &str, declarer: Option<Seat>) -> Result<Option<Contract>, ParseError> {
+    if s.eq_ignore_ascii_case("pass") {
+        return Ok(None);
+    }
+
+    let bid = s.trim_end_matches(['X', 'x']);
+    let status = match s.len() - bid.len() {
+        0 => Status::Undoubled,
+        1 => Status::Doubled,
+        2 => Status::Redoubled,
+        _ => Err(ParseError::TooLong)?,
+    };
+
+    Ok(Some(Contract {
+        bid: bid.parse()?,
+        status,
+        declarer: declarer.ok_or(ParseError::SeatNotValid)?,
+    }))
+}
+
+fn write_contract(contract: &Contract) -> String {
+    let status = match contract.status {
+        Status::Undoubled => "",
+        Status::Doubled => "X",
+        Status::Redoubled => "XX",
+    };
+    format!("{}{}", write_contract_bid(&contract.bid), status)
+}
+
+fn write_contract_bid(bid: &ContractBid) -> String {
+    match bid.suit {
+        BiddingSuit::Suit(suit) => format!("{}{}", bid.level, char::from(suit)),
+        BiddingSuit::NoTrumps => format!("{}NT", bid.level),
+    }
+}
+
+fn parse_auction(dealer: Seat, section: &[String]) -> Result<Auction, ParseError> {
+    let mut auction = Auction::new(dealer);
+
+    for token in section.iter().flat_map(|line| line.split_whitespace()) {
+        match token {
+            // Note references, annotations, and the markers for an incomplete auction.
+            _ if token.starts_with('=') || token.starts_with('$') => {}
+            "*" | "+" | "-" => {}
+            "AP" => {
+                while !auction.closed() {
+                    auction
+                        .enter_bid(AuctionBid::Pass)
+                        .map_err(|_| ParseError::CallNotLegal)?;
+                }
+            }
+            _ => auction
+                .enter_bid(token.trim_end_matches(['!', '?']).parse()?)
+                .map_err(|_| ParseError::CallNotLegal)?,
+        }
+    }
+
+    Ok(auction)
+}
+
+fn write_call(call: &AuctionBid) -> String {
+    match call {
+        AuctionBid::Bid(bid) => write_contract_bid(bid),
+        AuctionBid::Pass => "Pass".to_string(),
+        AuctionBid::Double => "X".to_string(),
+        AuctionBid::Redouble => "XX".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const CLUB_NIGHT: &str = r##"% PBN 2.1
+% A comment line
+[Event "Club night"]
+[Site "Anytown"]
+[Board "1"]
+[Dealer "N"]
+[Vulnerable "None"]
+[Deal "N:AKQ2.J54.T9.8762 JT9.AKQ.AKQJ.AKQ 876.T9876.8765.J 543.32.432.T9543"]
+[Declarer "E"]
+[Contract "3NT"]
+[Result "10"]
+[Auction "N"]
+Pass 2C Pass 2D { waiting }
+Pass 3NT AP
+
+[Event "#"]
+[Board "2"]
+[Dealer "E"]
+[Vulnerable "NS"]
+[Deal "E:JT9.AKQ.AKQJ.AKQ 876.T9876.8765.J 543.32.432.T9543 AKQ2.J54.T9.8762"]
+[Contract "Pass"]
+[Auction "E"]
+AP
+"##;
+
+    #[test]
+    fn parse_games() {
+        let games = parse(CLUB_NIGHT).unwrap();
+        assert_eq!(2, games.len());
+
+        let first = &games[0];
+        assert_eq!(Some(1), first.board);
+        assert_eq!(Some(Seat::North), first.dealer);
+        assert_eq!(Some(Vulnerability::None), first.vulnerability);
+        assert_eq!(Some("E:3NT".parse().unwrap()), first.contract);
+        assert_eq!(Some(10), first.result);
+
+        let auction = first.auction.as_ref().unwrap();
+        assert!(auction.closed());
+        assert_eq!(auction.contract(), first.contract);
+
+        let deal = first.deal.as_ref().unwrap();
+        assert_eq!(10, deal[Seat::North as usize].hcp());
+        assert_eq!(29, deal[Seat::East as usize].hcp());
+        assert_eq!(52, deal.iter().map(Hand::len).sum::<usize>());
+
+        let second = &games[1];
+        assert_eq!(Some(Vulnerability::NorthSouth), second.vulnerability);
+        assert_eq!(first.deal, second.deal);
+        assert_eq!(None, second.contract);
+        assert_eq!(4, second.auction.as_ref().unwrap().sequence().len());
+        assert_eq!(
+            vec![Tag::new("Event", "Club night".to_string())],
+            second.tags
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let games = parse(CLUB_NIGHT).unwrap();
+        let written = write(&games);
+
+        assert_eq!(games, parse(&written).unwrap());
+        assert!(written.contains(
+            "[Deal \"N:AKQ2.J54.T9.8762 JT9.AKQ.AKQJ.AKQ 876.T9876.8765.J 543.32.432.T9543\"]"
+        ));
+        assert!(written.contains("[Auction \"N\"]\nPass 2C Pass 2D\nPass 3NT Pass Pass\nPass"));
+    }
+
+    #[test]
+    fn write_game() {
+        let mut auction = Auction::new(Seat::South);
+        for call in ["1NT", "X", "XX", "P", "P", "P"] {
+            auction.enter_bid(call.parse().unwrap()).unwrap();
+        }
+        let game = Game {
+            board: Some(3),
+            dealer: Some(Seat::South),
+            contract: auction.contract(),
+            auction: Some(auction),
+            result: Some(6),
+            tags: vec![Tag::new("Event", "Quote \"test\"".to_string())],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            "[Event \"Quote \\\"test\\\"\"]\n\
+             [Board \"3\"]\n\
+             [Dealer \"S\"]\n\
+             [Declarer \"S\"]\n\
+             [Contract \"1NTXX\"]\n\
+             [Result \"6\"]\n\
+             [Auction \"S\"]\n\
+             1NT X XX Pass\n\
+             Pass Pass",
+            game.to_string()
+        );
+        assert_eq!(vec![game.clone()], parse(&write(&[game])).unwrap());
+    }
+
+    #[test]
+    fn invalid_games() {
+        assert_eq!(
+            Err(ParseError::DealNotValid),
+            parse("[Deal \"N:AKQ2.J54.T9.8762 AKQ2.J54.T9.8762 - -\"]")
+        );
+        assert_eq!(
+            Err(ParseError::DealNotValid),
+            parse("[Deal \"N:AKQ2.J54.T9 - - -\"]")
+        );
+        assert_eq!(
+            Err(ParseError::CallNotLegal),
+            parse("[Auction \"N\"]\n1S 1H")
+        );
+        assert_eq!(Err(ParseError::TagNotValid), parse("[Board 1]"));
+        assert_eq!(Err(ParseError::TagNotValid), parse("[Board \"0\"]"));
+        assert_eq!(Err(ParseError::TagNotValid), parse("1S Pass"));
+        assert_eq!(
+            Err(ParseError::VulnerabilityNotValid),
+            parse("[Vulnerable \"Some\"]")
+        );
+    }
+}
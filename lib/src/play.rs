@@ -0,0 +1,265 @@
+use crate::{
+    auction::{BiddingSuit, Contract, Seat, Side},
+    card::{Card, Suit},
+    error::PlayError,
+    hand::Hand,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trick {
+    leader: Seat,
+    cards: Vec<Card>,
+}
+
+impl Trick {
+    pub fn new(leader: Seat) -> Self {
+        Self {
+            leader,
+            cards: vec![],
+        }
+    }
+
+    pub fn leader(&self) -> Seat {
+        self.leader
+    }
+
+    pub fn cards(&self) -> &[Card] {
+        &self.cards
+    }
+
+    /// Returns each card played to the trick alongside the seat that played it, in order
+    /// of play.
+    pub fn plays(&self) -> impl Iterator<Item = (Seat, Card)> + '_ {
+        self.cards
+            .iter()
+            .enumerate()
+            .map(|(idx, card)| (self.leader + idx, *card))
+    }
+
+    pub fn led_suit(&self) -> Option<Suit> {
+        self.cards.first().map(|card| card.suit)
+    }
+
+    pub fn turn(&self) -> Seat {
+        self.leader + self.cards.len()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.cards.len() == 4
+    }
+
+    /// Returns the seat which played the winning card of a complete trick: the highest
+    /// trump if any were played, or the highest card of the suit led otherwise.
+    pub fn winner(&self, strain: BiddingSuit) -> Option<Seat> {
+        if !self.is_complete() {
+            return None;
+        }
+
+        let led = self.led_suit()?;
+        let trumps = strain.trumps();
+
+        self.plays()
+            .filter(|(_, card)| card.suit == led || Some(card.suit) == trumps)
+            .max_by_key(|(_, card)| (Some(card.suit) == trumps, card.rank))
+            .map(|(seat, _)| seat)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Play {
+    contract: Contract,
+    hands: [Hand; 4],
+    current: Trick,
+    tricks: Vec<Trick>,
+}
+
+impl Play {
+    /// Begins the play of a hand. `hands` are indexed by seat, as returned by
+    /// [`crate::deck::Deck::deal`].
+    pub fn new(contract: Contract, hands: [Hand; 4]) -> Self {
+        Self {
+            contract,
+            hands,
+            current: Trick::new(contract.declarer + 1),
+            tricks: vec![],
+        }
+    }
+
+    pub fn contract(&self) -> &Contract {
+        &self.contract
+    }
+
+    pub fn declarer(&self) -> Seat {
+        self.contract.declarer
+    }
+
+    pub fn dummy(&self) -> Seat {
+        self.contract.declarer.partner()
+    }
+
+    pub fn opening_leader(&self) -> Seat {
+        self.contract.declarer + 1
+    }
+
+    /// Returns the cards remaining in the given seat's hand.
+    pub fn hand(&self, seat: Seat) -> &Hand {
+        &self.hands[seat as usize]
+    }
+
+    pub fn current_trick(&self) -> &Trick {
+        &self.current
+    }
+
+    /// Returns the tricks completed so far, in order of play.
+    pub fn tricks(&self) -> &[Trick] {
+        &self.tricks
+    }
+
+    pub fn turn(&self) -> Seat {
+        self.current.turn()
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.tricks.len() == 13
+    }
+
+    pub fn tricks_won(&self, side: Side) -> usize {
+        self.tricks
+            .iter()
+            .filter_map(|trick| trick.winner(self.contract.bid.suit))
+            .filter(|seat| seat.side() == side)
+            .count()
+    }
+
+    pub fn declarer_tricks(&self) -> usize {
+        self.tricks_won(self.declarer().side())
+    }
+
+    /// Returns the cards the seat on turn may legally play.
+    pub fn legal_cards(&self) -> Vec<Card> {
+        let hand = self.hand(self.turn());
+        match self.current.led_suit() {
+            Some(led) if !hand.suit(led).is_empty() => hand.suit(led).iter().collect(),
+            _ => hand.iter().collect(),
+        }
+    }
+
+    /// Plays a card on behalf of the seat on turn. When the card completes a trick, the
+    /// trick is gathered and the winner of it leads to the next one.
+    pub fn play_card(&mut self, card: Card) -> Result<(), PlayError> {
+        if self.is_finished() {
+            return Err(PlayError::PlayFinished);
+        }
+
+        let seat = self.turn();
+        let hand = &self.hands[seat as usize];
+        if !hand.contains(&card) {
+            return Err(PlayError::CardNotHeld);
+        }
+        if let Some(led) = self.current.led_suit() {
+            if card.suit != led && !hand.suit(led).is_empty() {
+                return Err(PlayError::MustFollowSuit);
+            }
+        }
+
+        self.hands[seat as usize].remove(&card);
+        self.current.cards.push(card);
+
+        if let Some(winner) = self.current.winner(self.contract.bid.suit) {
+            let trick = std::mem::replace(&mut self.current, Trick::new(winner));
+            self.tricks.push(trick);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::deck::Deck;
+
+    fn trick(leader: Seat, cards: &[&str]) -> Trick {
+        Trick {
+            leader,
+            cards: cards.iter().map(|card| card.parse().unwrap()).collect(),
+        }
+    }
+
+    #[test]
+    fn trick_winners() {
+        let no_trumps = BiddingSuit::NoTrumps;
+        let hearts = BiddingSuit::Suit(Suit::Hearts);
+
+        let small_lead = trick(Seat::West, &["2S", "AD", "KS", "3H"]);
+        assert_eq!(Some(Seat::East), small_lead.winner(no_trumps));
+        assert_eq!(Some(Seat::South), small_lead.winner(hearts));
+
+        let overruff = trick(Seat::North, &["AC", "2H", "7H", "KC"]);
+        assert_eq!(Some(Seat::North), overruff.winner(no_trumps));
+        assert_eq!(Some(Seat::South), overruff.winner(hearts));
+
+        assert_eq!(None, trick(Seat::North, &["AC", "2H"]).winner(hearts));
+    }
+
+    #[test]
+    fn opening_lead() {
+        let play = Play::new("S:4H".parse().unwrap(), Deck::default().deal().into());
+
+        assert_eq!(Seat::West, play.opening_leader());
+        assert_eq!(Seat::West, play.turn());
+        assert_eq!(Seat::North, play.dummy());
+    }
+
+    #[test]
+    fn following_suit() {
+        // An unshuffled deck deals North all the clubs, East the diamonds, South the
+        // hearts and West the spades.
+        let mut play = Play::new("N:1NT".parse().unwrap(), Deck::default().deal().into());
+
+        assert_eq!(
+            Err(PlayError::CardNotHeld),
+            play.play_card("AC".parse().unwrap())
+        );
+        assert_eq!(Ok(()), play.play_card("AD".parse().unwrap()));
+        assert_eq!(Seat::South, play.turn());
+        assert_eq!(13, play.legal_cards().len());
+
+        let mut hands: [Hand; 4] = Deck::default().deal().into();
+        hands[Seat::East as usize].remove(&"2D".parse().unwrap());
+        hands[Seat::South as usize] =
+            Hand::from(vec!["2D".parse().unwrap(), "3H".parse().unwrap()]);
+        let mut play = Play::new("N:1NT".parse().unwrap(), hands);
+        play.play_card("AD".parse().unwrap()).unwrap();
+
+        assert_eq!(vec!["2D".parse::<Card>().unwrap()], play.legal_cards());
+        assert_eq!(
+            Err(PlayError::MustFollowSuit),
+            play.play_card("3H".parse().unwrap())
+        );
+        assert_eq!(Ok(()), play.play_card("2D".parse().unwrap()));
+    }
+
+    #[test]
+    fn full_play() {
+        let mut play = Play::new("N:7SX".parse().unwrap(), Deck::default().deal().into());
+
+        while !play.is_finished() {
+            let card = play.legal_cards()[0];
+            play.play_card(card).unwrap();
+        }
+
+        // West holds every spade, so ruffs the opening lead and cashes the rest.
+        assert_eq!(13, play.tricks_won(Side::EastWest));
+        assert_eq!(0, play.declarer_tricks());
+        assert!(play
+            .tricks()
+            .iter()
+            .skip(1)
+            .all(|trick| trick.leader() == Seat::West));
+        assert_eq!(
+            Err(PlayError::PlayFinished),
+            play.play_card("2C".parse().unwrap())
+        );
+    }
+}
@@ -0,0 +1,619 @@
+//! Robots which play the cards, as declarer or as a defender.
+
+use std::cmp::Reverse;
+
+use rand::{seq::SliceRandom, Rng};
+
+use crate::{
+    auction::Seat,
+    card::{Card, Rank, Suit},
+    cardset::CardSet,
+    dds,
+    hand::Hand,
+    play::Play,
+};
+
+const SEATS: [Seat; 4] = [Seat::North, Seat::East, Seat::South, Seat::West];
+const SUITS: [Suit; 4] = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs];
+
+/// A robot which chooses the cards to play.
+pub trait CardPlayer {
+    /// Chooses a legal card for the seat on turn, which declarer chooses when dummy is on
+    /// turn. The choice should depend only on what the chooser can see: their own cards,
+    /// dummy's once the opening lead is made, and the cards played so far.
+    fn play(&self, play: &Play) -> Card;
+}
+
+/// A robot which plays single-dummy by the usual rules of thumb: leading the top of a
+/// sequence or fourth best, second hand low and third hand high, drawing trumps, cashing
+/// winners and finessing.
+///
+/// With `samples` set, it instead deals the cards it can't see that many times, in ways
+/// which agree with the suits each player has shown out of, and plays the card which
+/// takes the most tricks double-dummy over all of them. The rules of thumb then only
+/// break ties.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Robot {
+    pub samples: usize,
+}
+
+impl CardPlayer for Robot {
+    fn play(&self, play: &Play) -> Card {
+        self.play_with(play, &mut rand::rng())
+    }
+}
+
+impl Robot {
+    /// Returns a robot which samples the unseen cards `samples` times for each card.
+    pub fn monte_carlo(samples: usize) -> Self {
+        Self { samples }
+    }
+
+    /// Chooses a card as [`CardPlayer::play`] does, using the given source of randomness
+    /// to deal the unseen cards.
+    pub fn play_with<R: Rng + ?Sized>(&self, play: &Play, rng: &mut R) -> Card {
+        let view = View::new(play);
+        let preferred = view.choose();
+        let legal = play.legal_cards();
+        if self.samples == 0 || legal.len() == 1 {
+            return preferred;
+        }
+
+        let trick = play.current_trick();
+        let mut totals = vec![0; legal.len()];
+        for _ in 0..self.samples {
+            let hands = view.sample(rng);
+            let plays = dds::solve_plays(
+                &hands,
+                play.contract().bid.suit,
+                trick.leader(),
+                trick.cards(),
+            );
+            for (card, tricks) in plays {
+                let idx = legal.iter().position(|other| *other == card).unwrap();
+                totals[idx] += tricks as usize;
+            }
+        }
+
+        let best = *totals.iter().max().unwrap();
+        let idx = legal.iter().position(|card| *card == preferred).unwrap();
+        if totals[idx] == best {
+            return preferred;
+        }
+        // Of cards which do equally well, the lowest gives away the least.
+        legal
+            .iter()
+            .zip(&totals)
+            .filter(|(_, total)| **total == best)
+            .map(|(card, _)| *card)
+            .min_by_key(|card| card.rank)
+            .unwrap()
+    }
+}
+
+/// What the player choosing a card can see.
+struct View<'a> {
+    play: &'a Play,
+    /// The seat on turn.
+    seat: Seat,
+    /// The seat choosing the card, which is declarer when dummy is on turn.
+    chooser: Seat,
+    dummy_visible: bool,
+    trumps: Option<Suit>,
+    /// The cards yet to be played which the seat on turn's side might not hold.
+    outstanding: CardSet,
+}
+
+impl<'a> View<'a> {
+    fn new(play: &'a Play) -> Self {
+        let seat = play.turn();
+        let chooser = match seat == play.dummy() {
+            true => play.declarer(),
+            false => seat,
+        };
+        let dummy_visible = !play.tricks().is_empty() || !play.current_trick().cards().is_empty();
+
+        let mut view = Self {
+            play,
+            seat,
+            chooser,
+            dummy_visible,
+            trumps: play.contract().bid.suit.trumps(),
+            outstanding: CardSet::EMPTY,
+        };
+        for other in SEATS {
+            let ours = other == seat || (other == seat.partner() && view.visible(other));
+            if !ours {
+                view.outstanding |= play.hand(other).cards();
+            }
+        }
+        view
+    }
+
+    fn visible(&self, seat: Seat) -> bool {
+        seat == self.chooser || (seat == self.play.dummy() && self.dummy_visible)
+    }
+
+    fn hand(&self) -> &Hand {
+        self.play.hand(self.seat)
+    }
+
+    fn declaring(&self) -> bool {
+        self.seat.side() == self.play.declarer().side()
+    }
+
+    /// Returns the outstanding cards of the card's suit which outrank it.
+    fn above(&self, card: Card) -> CardSet {
+        self.outstanding
+            .suit(card.suit)
+            .iter()
+            .filter(|other| other.rank > card.rank)
+            .collect()
+    }
+
+    fn is_master(&self, card: Card) -> bool {
+        self.above(card).is_empty()
+    }
+
+    /// Returns the lowest card held in the seat which is equivalent to `card`, no card
+    /// outstanding coming between them.
+    fn lowest_equal(&self, card: Card) -> Card {
+        let mut lowest = card;
+        for other in self.hand().suit(card.suit).iter() {
+            if other.rank >= lowest.rank {
+                continue;
+            }
+            if self.above(other).len() != self.above(lowest).len() {
+                break;
+            }
+            lowest = other;
+        }
+        lowest
+    }
+
+    fn beats(&self, card: Card, winning: Card) -> bool {
+        match card.suit == winning.suit {
+            true => card.rank > winning.rank,
+            false => Some(card.suit) == self.trumps,
+        }
+    }
+
+    /// Returns the seat winning the current trick so far, and the card it won with.
+    fn winning(&self) -> Option<(Seat, Card)> {
+        self.play
+            .current_trick()
+            .plays()
+            .fold(None, |best, (seat, card)| match best {
+                Some((_, winning)) if !self.beats(card, winning) => best,
+                _ => Some((seat, card)),
+            })
+    }
+
+    fn choose(&self) -> Card {
+        if self.play.current_trick().cards().is_empty() {
+            self.lead()
+        } else {
+            self.follow()
+        }
+    }
+
+    /// The suits in which the seat holds cards, with any trumps last.
+    fn suits(&self) -> Vec<Suit> {
+        let mut suits: Vec<Suit> = SUITS
+            .into_iter()
+            .filter(|suit| !self.hand().suit(*suit).is_empty())
+            .collect();
+        suits.sort_by_key(|suit| Some(*suit) == self.trumps);
+        suits
+    }
+
+    /// Returns the card to lead in a suit: the top of a sequence, fourth best from a long
+    /// suit, low from three to an honour, and otherwise the top.
+    fn lead_from(&self, suit: Suit) -> Card {
+        let cards: Vec<Card> = self.hand().suit(suit).iter().collect();
+        let sequence = cards.len() >= 2
+            && cards[0].rank >= Rank::Ten
+            && self.lowest_equal(cards[0]) != cards[0];
+        match cards.len() {
+            _ if sequence => cards[0],
+            4.. => cards[3],
+            3 if cards[0].rank >= Rank::Jack => cards[2],
+            _ => cards[0],
+        }
+    }
+
+    fn lead(&self) -> Card {
+        if self.play.tricks().is_empty() {
+            self.opening_lead()
+        } else if self.declaring() {
+            self.declarer_lead()
+        } else {
+            self.defender_lead()
+        }
+    }
+
+    fn opening_lead(&self) -> Card {
+        let hand = self.hand();
+        let suits = self.suits();
+        let side: Vec<Suit> = match suits.iter().any(|suit| Some(*suit) != self.trumps) {
+            true => suits
+                .into_iter()
+                .filter(|suit| Some(*suit) != self.trumps)
+                .collect(),
+            false => suits,
+        };
+        let strength = |suit: &Suit| -> u8 {
+            hand.suit(*suit)
+                .iter()
+                .map(|card| card.rank.high_card_points())
+                .sum()
+        };
+
+        let Some(trumps) = self.trumps else {
+            // At no trumps, attack the longest suit.
+            let suit = side
+                .iter()
+                .max_by_key(|suit| (hand.suit(**suit).len(), strength(suit)))
+                .unwrap();
+            return self.lead_from(*suit);
+        };
+
+        let sequence = side
+            .iter()
+            .filter_map(|suit| {
+                let top = hand.suit(*suit).highest()?;
+                (top.rank >= Rank::Ten && self.lowest_equal(top) != top).then_some(top)
+            })
+            .max_by_key(|card| card.rank);
+        if let Some(card) = sequence {
+            return card;
+        }
+
+        // Look for a ruff, holding a trump to ruff with.
+        let singleton = side
+            .iter()
+            .find(|suit| hand.suit(**suit).len() == 1 && !hand.suit(trumps).is_empty());
+        if let Some(suit) = singleton {
+            return self.lead_from(*suit);
+        }
+
+        // Don't lead away from an ace against a suit contract.
+        let ace = |suit: &Suit| {
+            let cards = hand.suit(*suit);
+            cards.highest().unwrap().rank == Rank::Ace && cards.len() > 1
+        };
+        let suit = side
+            .iter()
+            .max_by_key(|suit| (!ace(suit), hand.suit(**suit).len(), Reverse(strength(suit))))
+            .unwrap();
+        self.lead_from(*suit)
+    }
+
+    fn declarer_lead(&self) -> Card {
+        let hand = self.hand();
+        let partner = self.play.hand(self.seat.partner());
+
+        // Draw trumps while the defenders hold any.
+        if let Some(trumps) = self.trumps {
+            let held = hand.suit(trumps);
+            if !self.outstanding.suit(trumps).is_empty() && !held.is_empty() {
+                let top = held.highest().unwrap();
+                return match self.is_master(top) {
+                    true => self.lowest_equal(top),
+                    false => held.lowest().unwrap(),
+                };
+            }
+        }
+
+        // Cash winners, and cross to partner's.
+        let suits = self.suits();
+        for suit in &suits {
+            let top = hand.suit(*suit).highest().unwrap();
+            if self.is_master(top) {
+                return self.lowest_equal(top);
+            }
+        }
+        for suit in &suits {
+            if partner
+                .suit(*suit)
+                .highest()
+                .is_some_and(|top| self.is_master(top))
+            {
+                return hand.suit(*suit).lowest().unwrap();
+            }
+        }
+
+        // Lead towards a tenace, such as the ace and queen with the king outstanding.
+        for suit in &suits {
+            let tenace = partner.suit(*suit).iter().any(|card| {
+                let above = self.above(card);
+                above.len() == 1
+                    && partner.suit(*suit).highest().unwrap().rank > above.highest().unwrap().rank
+            });
+            if tenace {
+                return hand.suit(*suit).lowest().unwrap();
+            }
+        }
+
+        let suit = suits
+            .iter()
+            .max_by_key(|suit| (Some(**suit) != self.trumps, hand.suit(**suit).len()))
+            .unwrap();
+        hand.suit(*suit).lowest().unwrap()
+    }
+
+    fn defender_lead(&self) -> Card {
+        let hand = self.hand();
+        let suits = self.suits();
+
+        for suit in &suits {
+            let top = hand.suit(*suit).highest().unwrap();
+            if self.is_master(top) {
+                return self.lowest_equal(top);
+            }
+        }
+
+        // Return the suit partner led first: high from two, otherwise low.
+        let partner = self.seat.partner();
+        let led = self
+            .play
+            .tricks()
+            .iter()
+            .find(|trick| trick.leader() == partner)
+            .and_then(|trick| trick.led_suit());
+        if let Some(suit) = led {
+            let cards = hand.suit(suit);
+            match cards.len() {
+                0 => {}
+                1 | 2 => return cards.highest().unwrap(),
+                _ => return cards.lowest().unwrap(),
+            }
+        }
+
+        let suit = suits
+            .iter()
+            .max_by_key(|suit| (Some(**suit) != self.trumps, hand.suit(**suit).len()))
+            .unwrap();
+        self.lead_from(*suit)
+    }
+
+    fn follow(&self) -> Card {
+        let hand = self.hand();
+        let trick = self.play.current_trick();
+        let led = trick.led_suit().unwrap();
+        let position = trick.cards().len();
+        let (winner, winning) = self.winning().unwrap();
+
+        // Partner's card takes the trick if nobody still to play can beat it.
+        let safe = winner == self.seat.partner() && (position == 3 || self.is_master(winning));
+        let cheapest_winner = |cards: CardSet| {
+            cards
+                .iter()
+                .filter(|card| self.beats(*card, winning))
+                .min_by_key(|card| card.rank)
+        };
+
+        let follows = hand.suit(led);
+        if follows.is_empty() {
+            let ruff = match self.trumps {
+                Some(trumps) if !safe => cheapest_winner(hand.suit(trumps)),
+                _ => None,
+            };
+            return ruff.unwrap_or_else(|| self.discard());
+        }
+
+        let low = follows.lowest().unwrap();
+        match position {
+            // Second hand low.
+            1 => low,
+            _ if safe => low,
+            // Declarer finesses, playing the cheapest card which wins the trick for now.
+            2 if self.declaring() => cheapest_winner(follows).unwrap_or(low),
+            // Third hand high, playing the lowest of equal cards.
+            2 => {
+                let high = self.lowest_equal(follows.highest().unwrap());
+                match self.beats(high, winning) {
+                    true => high,
+                    false => low,
+                }
+            }
+            _ => cheapest_winner(follows).unwrap_or(low),
+        }
+    }
+
+    /// Returns a low card from a suit which is neither trumps nor a winner, keeping length
+    /// where possible.
+    fn discard(&self) -> Card {
+        let hand = self.hand();
+        let cards: Vec<Card> = hand.iter().collect();
+        let preferred = |card: &&Card| Some(card.suit) != self.trumps && !self.is_master(**card);
+        let candidates: Vec<&Card> = match cards.iter().any(|card| preferred(&card)) {
+            true => cards.iter().filter(preferred).collect(),
+            false => cards.iter().collect(),
+        };
+        **candidates
+            .iter()
+            .min_by_key(|card| (card.rank, Reverse(hand.suit(card.suit).len())))
+            .unwrap()
+    }
+
+    /// Returns the suits each seat has shown out of, by failing to follow suit.
+    fn voids(&self) -> [Vec<Suit>; 4] {
+        let mut voids: [Vec<Suit>; 4] = Default::default();
+        let tricks = self.play.tricks().iter().chain([self.play.current_trick()]);
+        for trick in tricks {
+            let Some(led) = trick.led_suit() else {
+                continue;
+            };
+            for (seat, card) in trick.plays() {
+                if card.suit != led && !voids[seat as usize].contains(&led) {
+                    voids[seat as usize].push(led);
+                }
+            }
+        }
+        voids
+    }
+
+    /// Deals the cards the chooser can't see to the seats holding them, as many to each as
+    /// it holds, avoiding suits it has shown out of.
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> [Hand; 4] {
+        let hidden: Vec<Seat> = SEATS
+            .into_iter()
+            .filter(|seat| !self.visible(*seat))
+            .collect();
+        let unseen: Vec<Card> = hidden
+            .iter()
+            .flat_map(|seat| self.play.hand(*seat).iter())
+            .collect();
+        let voids = self.voids();
+        let may_hold = |seat: Seat, card: &Card| !voids[seat as usize].contains(&card.suit);
+
+        let mut hands = SEATS.map(|seat| match self.visible(seat) {
+            true => self.play.hand(seat).cards(),
+            false => CardSet::EMPTY,
+        });
+        // Deal at random, keeping the first deal which respects the voids, or the last
+        // one tried.
+        for attempt in 0..100 {
+            let mut cards = unseen.clone();
+            cards.shuffle(rng);
+            let mut dealt = hands;
+            // Cards which only one seat may hold go to it first.
+            cards.sort_by_key(|card| hidden.iter().filter(|seat| may_hold(**seat, card)).count());
+            for card in cards {
+                let seat = hidden
+                    .iter()
+                    .filter(|seat| dealt[**seat as usize].len() < self.play.hand(**seat).len())
+                    .find(|seat| may_hold(**seat, &card))
+                    .or_else(|| {
+                        hidden.iter().find(|seat| {
+                            dealt[**seat as usize].len() < self.play.hand(**seat).len()
+                        })
+                    })
+                    .unwrap();
+                dealt[*seat as usize].insert(card);
+            }
+            let respected = hidden.iter().all(|seat| {
+                dealt[*seat as usize]
+                    .iter()
+                    .all(|card| may_hold(*seat, &card))
+            });
+            if respected || attempt == 99 {
+                hands = dealt;
+                break;
+            }
+        }
+        hands.map(Hand::from)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::*;
+    use crate::{auction::Side, deck::Deck};
+
+    fn play(contract: &str, hands: [&str; 4]) -> Play {
+        let hands = hands.map(|hand| hand.parse().unwrap());
+        Play::new(contract.parse().unwrap(), hands)
+    }
+
+    fn card(card: &str) -> Card {
+        card.parse().unwrap()
+    }
+
+    const HANDS: [&str; 4] = [
+        "AQ52.K84.KJ3.Q92",
+        "J43.QJT9.Q87.KJ4",
+        "K96.A652.A52.A63",
+        "T87.73.T964.T875",
+    ];
+
+    #[test]
+    fn opening_leads() {
+        let robot = Robot::default();
+        assert_eq!(card("QH"), robot.play(&play("N:3NT", HANDS)));
+
+        let hands = [
+            "AQ52.K84.KJ3.Q92",
+            "J43.QT92.Q87.KJ4",
+            "K96.AJ65.A52.A63",
+            "T87.73.T964.T875",
+        ];
+        assert_eq!(card("2H"), robot.play(&play("N:3NT", hands)));
+
+        // Against a suit contract, a singleton, holding trumps to ruff with.
+        let hands = [
+            "AQ52.K84.KJ3.Q96",
+            "4.QT93.Q872.K542",
+            "KJ963.A65.A5.A73",
+            "T87.J72.T964.JT8",
+        ];
+        assert_eq!(card("4S"), robot.play(&play("N:4H", hands)));
+    }
+
+    #[test]
+    fn following() {
+        let robot = Robot::default();
+        let mut play = play("N:3NT", HANDS);
+        play.play_card(card("QH")).unwrap();
+        // Second hand low.
+        assert_eq!(card("2H"), robot.play(&play));
+        play.play_card(card("2H")).unwrap();
+        // Third hand would play high, but can't beat partner's queen.
+        assert_eq!(card("3H"), robot.play(&play));
+        play.play_card(card("3H")).unwrap();
+        // Fourth hand wins as cheaply as it can.
+        assert_eq!(card("KH"), robot.play(&play));
+    }
+
+    /// Returns the play of an ending after South wins the first trick, with North's ace
+    /// and queen of spades over East's king.
+    fn finesse() -> Play {
+        let mut play = play("S:1NT", ["AQ.2..", "K3.3..", "54.A..", "76.4.."]);
+        for card in ["4H", "2H", "3H", "AH"] {
+            play.play_card(self::card(card)).unwrap();
+        }
+        play
+    }
+
+    #[test]
+    fn declarer_play() {
+        let robot = Robot::default();
+        let mut play = finesse();
+        // South leads towards North's tenace, and North finesses.
+        assert_eq!(card("4S"), robot.play(&play));
+        play.play_card(card("4S")).unwrap();
+        play.play_card(card("6S")).unwrap();
+        assert_eq!(card("QS"), robot.play(&play));
+    }
+
+    #[test]
+    fn monte_carlo() {
+        let mut play = finesse();
+        play.play_card(card("4S")).unwrap();
+        play.play_card(card("6S")).unwrap();
+        // The finesse wins when West holds the king, while the ace never takes more than
+        // one trick.
+        let mut rng = StdRng::seed_from_u64(1);
+        assert_eq!(card("QS"), Robot::monte_carlo(8).play_with(&play, &mut rng));
+    }
+
+    #[test]
+    fn complete_deals() {
+        for seed in 0..10 {
+            let hands = Deck::from_seed(seed).deal();
+            let mut play = Play::new("S:3NT".parse().unwrap(), hands.into());
+            while !play.is_finished() {
+                let card = Robot::default().play(&play);
+                assert!(play.legal_cards().contains(&card));
+                play.play_card(card).unwrap();
+            }
+            assert_eq!(
+                13,
+                play.tricks_won(Side::NorthSouth) + play.tricks_won(Side::EastWest)
+            );
+        }
+    }
+}
@@ -0,0 +1,292 @@
+use std::fmt::Display;
+
+use strum::IntoEnumIterator;
+
+use crate::{
+    auction::{Contract, Side},
+    card::{Card, Rank, Suit},
+    error::RubberError,
+    hand::Hand,
+};
+
+/// A rubber bridge score sheet. North-South score in the "We" column, and East-West in
+/// the "They" column.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Rubber {
+    above: [Vec<i32>; 2],
+    below: Vec<[Vec<i32>; 2]>,
+}
+
+impl Display for Rubber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn row(we: Option<&i32>, they: Option<&i32>) -> String {
+            let cell = |score: Option<&i32>| score.map(i32::to_string).unwrap_or_default();
+            format!("{:>8}  |  {:<8}", cell(we), cell(they))
+                .trim_end()
+                .to_string()
+        }
+        let divider = format!("{0}+{0}", "-".repeat(10));
+        let line = format!("{0}+{0}", "=".repeat(10));
+
+        let mut rows = vec![format!("{:>8}  |  {}", "WE", "THEY"), divider.clone()];
+
+        // Scores above the line accumulate upwards from it.
+        let [we, they] = &self.above;
+        let height = we.len().max(they.len());
+        for idx in (0..height).rev() {
+            rows.push(row(we.get(idx), they.get(idx)));
+        }
+        rows.push(line);
+
+        for (idx, [we, they]) in self.below.iter().enumerate() {
+            for idx in 0..we.len().max(they.len()) {
+                rows.push(row(we.get(idx), they.get(idx)));
+            }
+            if idx + 1 < self.below.len() {
+                rows.push(divider.clone());
+            }
+        }
+
+        rows.push(divider);
+        rows.push(row(
+            Some(&self.total(Side::NorthSouth)),
+            Some(&self.total(Side::EastWest)),
+        ));
+
+        f.write_str(&rows.join("\n"))
+    }
+}
+
+impl Rubber {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn game_winner(game: &[Vec<i32>; 2]) -> Option<Side> {
+        [Side::NorthSouth, Side::EastWest]
+            .into_iter()
+            .find(|side| game[*side as usize].iter().sum::<i32>() >= 100)
+    }
+
+    pub fn games_won(&self, side: Side) -> usize {
+        self.below
+            .iter()
+            .filter(|game| Self::game_winner(game) == Some(side))
+            .count()
+    }
+
+    pub fn vulnerable(&self, side: Side) -> bool {
+        self.games_won(side) > 0
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.winner().is_some()
+    }
+
+    pub fn winner(&self) -> Option<Side> {
+        [Side::NorthSouth, Side::EastWest]
+            .into_iter()
+            .find(|side| self.games_won(*side) == 2)
+    }
+
+    /// Returns the points scored by the given side so far, above and below the line.
+    pub fn total(&self, side: Side) -> i32 {
+        self.above[side as usize].iter().sum::<i32>()
+            + self
+                .below
+                .iter()
+                .map(|game| game[side as usize].iter().sum::<i32>())
+                .sum::<i32>()
+    }
+
+    /// Returns the honours bonus held by the declaring side: 100 for four of the five
+    /// trump honours in one hand, or 150 for all five (or all four aces at no trumps).
+    pub fn honours(contract: &Contract, hands: &[Hand; 4]) -> i32 {
+        [contract.declarer, contract.declarer.partner()]
+            .iter()
+            .map(|seat| {
+                let hand = &hands[*seat as usize];
+                match contract.bid.suit.trumps() {
+                    Some(suit) => {
+                        let honours = [Rank::Ace, Rank::King, Rank::Queen, Rank::Jack, Rank::Ten]
+                            .into_iter()
+                            .filter(|rank| hand.contains(&Card { suit, rank: *rank }))
+                            .count();
+                        match honours {
+                            5 => 150,
+                            4 => 100,
+                            _ => 0,
+                        }
+                    }
+                    None if Suit::iter().all(|suit| {
+                        hand.contains(&Card {
+                            suit,
+                            rank: Rank::Ace,
+                        })
+                    }) =>
+                    {
+                        150
+                    }
+                    None => 0,
+                }
+            })
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Records the result of a contract, given the total number of tricks declarer took
+    /// and the four hands (indexed by seat) it was played with.
+    pub fn record(
+        &mut self,
+        contract: Contract,
+        tricks: u8,
+        hands: &[Hand; 4],
+    ) -> Result<(), RubberError> {
+        if self.is_finished() {
+            return Err(RubberError::RubberFinished);
+        }
+
+        let side = contract.declarer.side();
+        let vulnerable = self.vulnerable(side);
+        let mut above = vec![Self::honours(&contract, hands)];
+
+        match tricks.checked_sub(contract.target()) {
+            Some(overtricks) => {
+                if self
+                    .below
+                    .last()
+                    .is_none_or(|game| Self::game_winner(game).is_some())
+                {
+                    self.below.push(Default::default());
+                }
+                self.below.last_mut().unwrap()[side as usize].push(contract.trick_score());
+
+                above.push(contract.overtrick_score(vulnerable, overtricks));
+                above.push(contract.slam_bonus(vulnerable));
+                above.push(contract.insult_bonus());
+            }
+            None => {
+                self.above[side.opponents() as usize]
+                    .push(contract.undertrick_penalty(vulnerable, contract.target() - tricks));
+            }
+        }
+        self.above[side as usize].extend(above.into_iter().filter(|score| *score > 0));
+
+        if let Some(winner) = self.winner() {
+            let bonus = match self.games_won(winner.opponents()) {
+                0 => 700,
+                _ => 500,
+            };
+            self.above[winner as usize].push(bonus);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn no_honours() -> [Hand; 4] {
+        std::array::from_fn(|_| Hand::from(vec![]))
+    }
+
+    fn record(rubber: &mut Rubber, contract: &str, tricks: u8) {
+        rubber
+            .record(contract.parse().unwrap(), tricks, &no_honours())
+            .unwrap();
+    }
+
+    #[test]
+    fn two_game_rubber() {
+        let mut rubber = Rubber::new();
+
+        record(&mut rubber, "S:4S", 10);
+        assert!(rubber.vulnerable(Side::NorthSouth));
+        assert!(!rubber.vulnerable(Side::EastWest));
+
+        record(&mut rubber, "E:2H", 7);
+        record(&mut rubber, "N:3NT", 10);
+
+        assert_eq!(Some(Side::NorthSouth), rubber.winner());
+        assert_eq!(120 + 50 + 100 + 30 + 700, rubber.total(Side::NorthSouth));
+        assert_eq!(0, rubber.total(Side::EastWest));
+        assert_eq!(
+            Err(RubberError::RubberFinished),
+            rubber.record("N:1C".parse().unwrap(), 7, &no_honours())
+        );
+    }
+
+    #[test]
+    fn part_scores_and_three_games() {
+        let mut rubber = Rubber::new();
+
+        record(&mut rubber, "N:2H", 8);
+        record(&mut rubber, "W:2S", 9);
+        assert_eq!(0, rubber.games_won(Side::EastWest));
+        record(&mut rubber, "E:2D", 8);
+        assert_eq!(1, rubber.games_won(Side::EastWest));
+
+        // North-South's part-score no longer counts towards the next game.
+        record(&mut rubber, "S:1NT", 7);
+        assert_eq!(0, rubber.games_won(Side::NorthSouth));
+        record(&mut rubber, "S:5CX", 10);
+        record(&mut rubber, "S:4H", 10);
+        assert_eq!(1, rubber.games_won(Side::NorthSouth));
+        assert!(!rubber.is_finished());
+
+        record(&mut rubber, "W:6S", 12);
+        assert_eq!(Some(Side::EastWest), rubber.winner());
+        assert_eq!(60 + 40 + 120, rubber.total(Side::NorthSouth));
+        assert_eq!(
+            60 + 30 + 40 + 100 + 180 + 750 + 500,
+            rubber.total(Side::EastWest)
+        );
+    }
+
+    #[test]
+    fn honours() {
+        let hands = ["AH KH QH JH 2C", "AD AC 3C", "TH 4C", "AS KS QS JS TS"].map(|cards| {
+            Hand::from(
+                cards
+                    .split_whitespace()
+                    .map(|card| card.parse().unwrap())
+                    .collect::<Vec<Card>>(),
+            )
+        });
+
+        assert_eq!(100, Rubber::honours(&"S:4H".parse().unwrap(), &hands));
+        assert_eq!(0, Rubber::honours(&"E:4H".parse().unwrap(), &hands));
+        assert_eq!(150, Rubber::honours(&"W:4S".parse().unwrap(), &hands));
+        assert_eq!(0, Rubber::honours(&"W:3NT".parse().unwrap(), &hands));
+
+        let mut rubber = Rubber::new();
+        rubber.record("N:4H".parse().unwrap(), 9, &hands).unwrap();
+        assert_eq!(100, rubber.total(Side::NorthSouth));
+        assert_eq!(50, rubber.total(Side::EastWest));
+    }
+
+    #[test]
+    fn score_sheet() {
+        let mut rubber = Rubber::new();
+        record(&mut rubber, "N:2H", 9);
+        record(&mut rubber, "E:3C", 8);
+        record(&mut rubber, "W:4S", 10);
+        record(&mut rubber, "N:1NT", 7);
+
+        assert_eq!(
+            "      WE  |  THEY\n\
+             ----------+----------\n\
+             \x20     50  |\n\
+             \x20     30  |\n\
+             ==========+==========\n\
+             \x20     60  |  120\n\
+             ----------+----------\n\
+             \x20     40  |\n\
+             ----------+----------\n\
+             \x20    180  |  120",
+            rubber.to_string()
+        );
+    }
+}
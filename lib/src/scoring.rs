@@ -0,0 +1,296 @@
+use crate::{
+    auction::{BiddingSuit, Contract, Status},
+    card::Suit,
+};
+
+impl Contract {
+    pub fn level(&self) -> u8 {
+        self.bid.level.get()
+    }
+
+    /// The number of tricks declarer needs to make the contract.
+    pub fn target(&self) -> u8 {
+        self.level() + 6
+    }
+
+    fn multiplier(&self) -> i32 {
+        match self.status {
+            Status::Undoubled => 1,
+            Status::Doubled => 2,
+            Status::Redoubled => 4,
+        }
+    }
+
+    /// Returns the points scored for the contracted tricks alone (the points that would
+    /// be scored below the line in rubber bridge), taking doubling into account.
+    pub fn trick_score(&self) -> i32 {
+        let level = self.level() as i32;
+        let undoubled = match self.bid.suit {
+            BiddingSuit::NoTrumps => 40 + 30 * (level - 1),
+            BiddingSuit::Suit(Suit::Spades | Suit::Hearts) => 30 * level,
+            BiddingSuit::Suit(Suit::Diamonds | Suit::Clubs) => 20 * level,
+        };
+        undoubled * self.multiplier()
+    }
+
+    pub fn is_game(&self) -> bool {
+        self.trick_score() >= 100
+    }
+
+    /// Returns the points scored for the given number of tricks made over the contract.
+    pub fn overtrick_score(&self, vulnerable: bool, overtricks: u8) -> i32 {
+        let per_trick = match (self.status, self.bid.suit) {
+            (Status::Undoubled, BiddingSuit::Suit(Suit::Diamonds | Suit::Clubs)) => 20,
+            (Status::Undoubled, _) => 30,
+            (_, _) => self.multiplier() / 2 * if vulnerable { 200 } else { 100 },
+        };
+        per_trick * overtricks as i32
+    }
+
+    /// Returns the bonus for bidding and making a small or grand slam.
+    pub fn slam_bonus(&self, vulnerable: bool) -> i32 {
+        match (self.level(), vulnerable) {
+            (6, false) => 500,
+            (6, true) => 750,
+            (7, false) => 1000,
+            (7, true) => 1500,
+            _ => 0,
+        }
+    }
+
+    /// Returns the bonus for making a doubled or redoubled contract.
+    pub fn insult_bonus(&self) -> i32 {
+        match self.status {
+            Status::Undoubled => 0,
+            Status::Doubled => 50,
+            Status::Redoubled => 100,
+        }
+    }
+
+    /// Returns the bonuses scored for making the contract in duplicate: the game or
+    /// part-score bonus, any slam bonus, and the bonus for making a doubled or
+    /// redoubled contract.
+    pub fn making_bonus(&self, vulnerable: bool) -> i32 {
+        let game = match (self.is_game(), vulnerable) {
+            (false, _) => 50,
+            (true, false) => 300,
+            (true, true) => 500,
+        };
+        game + self.slam_bonus(vulnerable) + self.insult_bonus()
+    }
+
+    /// Returns the (positive) number of points the defenders score when the contract
+    /// goes down by the given number of tricks.
+    pub fn undertrick_penalty(&self, vulnerable: bool, undertricks: u8) -> i32 {
+        (1..=undertricks as i32)
+            .map(|undertrick| match (self.status, vulnerable, undertrick) {
+                (Status::Undoubled, false, _) => 50,
+                (Status::Undoubled, true, _) => 100,
+                (_, false, 1) => 100 * self.multiplier() / 2,
+                (_, false, 2 | 3) => 200 * self.multiplier() / 2,
+                (_, true, 1) => 200 * self.multiplier() / 2,
+                (_, _, _) => 300 * self.multiplier() / 2,
+            })
+            .sum()
+    }
+
+    /// Returns the duplicate score for the contract, from the declaring side's point of
+    /// view, given the total number of tricks declarer took.
+    pub fn score(&self, vulnerable: bool, tricks: u8) -> i32 {
+        match tricks.checked_sub(self.target()) {
+            Some(overtricks) => {
+                self.trick_score()
+                    + self.overtrick_score(vulnerable, overtricks)
+                    + self.making_bonus(vulnerable)
+            }
+            None => -self.undertrick_penalty(vulnerable, self.target() - tricks),
+        }
+    }
+}
+
+/// The smallest score difference worth each number of IMPs from 1 to 24.
+const IMP_TABLE: [i32; 24] = [
+    20, 50, 90, 130, 170, 220, 270, 320, 370, 430, 500, 600, 750, 900, 1100, 1300, 1500, 1750,
+    2000, 2250, 2500, 3000, 3500, 4000,
+];
+
+/// Converts a difference between two duplicate scores to International Match Points, by
+/// the standard table.
+pub fn imps(difference: i32) -> i32 {
+    let imps = IMP_TABLE
+        .iter()
+        .take_while(|threshold| difference.abs() >= **threshold)
+        .count() as i32;
+    imps * difference.signum()
+}
+
+/// Returns the matchpoints for each of a board's results, given the scores from one
+/// side's point of view: a point for every other result it beats and half a point for
+/// every one it ties with, so that a top is one fewer than the number of results.
+pub fn matchpoints(scores: &[i32]) -> Vec<f64> {
+    scores
+        .iter()
+        .enumerate()
+        .map(|(idx, score)| {
+            scores
+                .iter()
+                .enumerate()
+                .filter(|(other, _)| *other != idx)
+                .map(|(_, other)| match score.cmp(other) {
+                    std::cmp::Ordering::Greater => 1.0,
+                    std::cmp::Ordering::Equal => 0.5,
+                    std::cmp::Ordering::Less => 0.0,
+                })
+                .sum()
+        })
+        .collect()
+}
+
+/// Returns matchpoints as [`matchpoints`] does, scaled by Neuberg's formula to the top of
+/// a board played `expected` times, for boards played fewer (or more) times than the
+/// rest.
+pub fn neuberg(scores: &[i32], expected: usize) -> Vec<f64> {
+    let ratio = expected as f64 / scores.len() as f64;
+    matchpoints(scores)
+        .into_iter()
+        .map(|points| (points + 0.5) * ratio - 0.5)
+        .collect()
+}
+
+/// Returns the IMPs for each of a board's results, given the scores from one side's point
+/// of view, compared with every other result in turn and averaged over the comparisons.
+pub fn cross_imps(scores: &[i32]) -> Vec<f64> {
+    let comparisons = scores.len().saturating_sub(1).max(1) as f64;
+    scores
+        .iter()
+        .map(|score| {
+            let total: i32 = scores.iter().map(|other| imps(score - other)).sum();
+            total as f64 / comparisons
+        })
+        .collect()
+}
+
+/// Returns the datum for Butler scoring: the average of a board's scores, leaving out the
+/// highest and lowest when there are more than two, rounded to the nearest 10.
+pub fn datum(scores: &[i32]) -> i32 {
+    let mut sorted = scores.to_vec();
+    sorted.sort();
+    let counted = match sorted.len() {
+        0 => return 0,
+        1 | 2 => &sorted[..],
+        len => &sorted[1..len - 1],
+    };
+    let average = counted.iter().sum::<i32>() as f64 / counted.len() as f64;
+    (average / 10.0).round() as i32 * 10
+}
+
+/// Returns the IMPs for each of a board's results against the board's [`datum`], given
+/// the scores from one side's point of view.
+pub fn butler(scores: &[i32]) -> Vec<i32> {
+    let datum = datum(scores);
+    scores.iter().map(|score| imps(score - datum)).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn score(contract: &str, vulnerable: bool, tricks: u8) -> i32 {
+        contract
+            .parse::<Contract>()
+            .unwrap()
+            .score(vulnerable, tricks)
+    }
+
+    #[test]
+    fn part_scores() {
+        assert_eq!(70, score("N:1C", false, 7));
+        assert_eq!(90, score("N:1NT", true, 7));
+        assert_eq!(110, score("E:2S", false, 8));
+        assert_eq!(110, score("E:3D", true, 9));
+        assert_eq!(130, score("S:3D", true, 10));
+        assert_eq!(150, score("W:2NT", false, 9));
+    }
+
+    #[test]
+    fn games() {
+        assert_eq!(420, score("S:4S", false, 10));
+        assert_eq!(620, score("S:4S", true, 10));
+        assert_eq!(430, score("N:3NT", false, 10));
+        assert_eq!(660, score("N:3NT", true, 11));
+        assert_eq!(400, score("W:5C", false, 11));
+        assert_eq!(600, score("W:5C", true, 11));
+    }
+
+    #[test]
+    fn slams() {
+        assert_eq!(980, score("N:6H", false, 12));
+        assert_eq!(1370, score("N:6C", true, 12));
+        assert_eq!(1520, score("E:7NT", false, 13));
+        assert_eq!(2220, score("E:7NT", true, 13));
+        assert_eq!(1470, score("S:6NT", true, 13));
+    }
+
+    #[test]
+    fn doubled_and_redoubled_makes() {
+        assert_eq!(180, score("N:1NTX", false, 7));
+        assert_eq!(280, score("N:1NTX", false, 8));
+        assert_eq!(380, score("N:1NTX", true, 8));
+        assert_eq!(470, score("W:2HX", false, 8));
+        assert_eq!(670, score("W:2HX", true, 8));
+        assert_eq!(230, score("S:1CXX", false, 7));
+        assert_eq!(1030, score("S:1CXX", true, 9));
+        assert_eq!(2980, score("E:7NTXX", true, 13));
+    }
+
+    #[test]
+    fn undertricks() {
+        assert_eq!(-100, score("N:3NT", true, 8));
+        assert_eq!(-150, score("N:3NT", false, 6));
+        assert_eq!(-100, score("S:4SX", false, 9));
+        assert_eq!(-500, score("S:4SX", false, 7));
+        assert_eq!(-800, score("S:4SX", true, 7));
+        assert_eq!(-800, score("S:4SX", false, 6));
+        assert_eq!(-1100, score("S:4SX", true, 6));
+        assert_eq!(-1600, score("S:4SXX", false, 6));
+        assert_eq!(-400, score("E:1HXX", true, 6));
+        assert_eq!(-3800, score("W:7NTX", true, 0));
+    }
+
+    #[test]
+    fn imp_table() {
+        assert_eq!(0, imps(0));
+        assert_eq!(0, imps(10));
+        assert_eq!(1, imps(20));
+        assert_eq!(-1, imps(-40));
+        assert_eq!(9, imps(420));
+        assert_eq!(10, imps(430));
+        assert_eq!(13, imps(750));
+        assert_eq!(-19, imps(-2160));
+        assert_eq!(24, imps(4000));
+        assert_eq!(24, imps(7600));
+    }
+
+    #[test]
+    fn matchpointing() {
+        assert_eq!(vec![2.5, 2.5, 1.0, 0.0], matchpoints(&[100, 100, 50, -50]));
+        assert_eq!(vec![0.0], matchpoints(&[420]));
+
+        // A board played three times rather than four.
+        let adjusted = neuberg(&[420, 170, 420], 4);
+        assert!((adjusted[0] - 13.0 / 6.0).abs() < 1e-9);
+        assert!((adjusted[1] - 1.0 / 6.0).abs() < 1e-9);
+        assert_eq!(matchpoints(&[420, 170]), neuberg(&[420, 170], 2));
+    }
+
+    #[test]
+    fn imping() {
+        assert_eq!(vec![8.0, 0.0, -8.0], cross_imps(&[420, 170, -50]));
+
+        let scores = [620, 170, 140, -100, 650];
+        assert_eq!(310, datum(&scores));
+        assert_eq!(vec![7, -4, -5, -9, 8], butler(&scores));
+        assert_eq!(400, datum(&[420, 400, 380]));
+        assert_eq!(0, datum(&[]));
+    }
+}
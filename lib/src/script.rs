@@ -0,0 +1,507 @@
+//! A small language for constraints on deals, in the spirit of Hans van Staveren's Dealer,
+//! for example:
+//!
+//! ```text
+//! hcp(south) >= 15 && hcp(south) <= 17
+//!     && shape(south, any 4333 + any 4432 + any 5332)
+//!     && spades(north) >= 4
+//! ```
+//!
+//! As in Dealer, every expression has an integer value, and conditions are true when
+//! non-zero. The functions are:
+//!
+//! - `hcp(seat)`: the seat's high card points
+//! - `spades(seat)`, `hearts(seat)`, `diamonds(seat)` and `clubs(seat)`: the seat's length
+//!   in the suit
+//! - `shape(seat, patterns)`: whether the seat's hand has one of the given shapes, joined
+//!   by `+`. A shape gives the lengths of spades, hearts, diamonds and clubs in that order,
+//!   or in any order after `any`, with `x` for any length: `5xxx` or `any 4333`
+//! - `hascard(seat, card)`: whether the seat holds the card, such as `AS` or `TC`
+//!
+//! along with integer arithmetic (`+ - * / %`, where division by zero gives zero),
+//! comparisons (`== != < <= > >=`) and logic (`&& || !`).
+
+use std::str::FromStr;
+
+use crate::{
+    auction::Seat,
+    card::{Card, Suit},
+    error::{ScriptError, ScriptErrorKind},
+    hand::Hand,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TokenKind {
+    Number(i64),
+    Word,
+    Symbol(&'static str),
+    End,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Token {
+    kind: TokenKind,
+    text: String,
+    /// The (1-based) column of the token's first character.
+    column: usize,
+}
+
+const SYMBOLS: [&str; 17] = [
+    "&&", "||", "==", "!=", "<=", ">=", "<", ">", "!", "+", "-", "*", "/", "%", "(", ")", ",",
+];
+
+fn tokenize(script: &str) -> Result<Vec<Token>, ScriptError> {
+    let chars: Vec<char> = script.chars().collect();
+    let mut tokens = vec![];
+    let mut idx = 0;
+
+    while idx < chars.len() {
+        let column = idx + 1;
+        if chars[idx].is_whitespace() {
+            idx += 1;
+        } else if chars[idx].is_ascii_alphanumeric() {
+            let start = idx;
+            while idx < chars.len() && (chars[idx].is_ascii_alphanumeric() || chars[idx] == '_') {
+                idx += 1;
+            }
+            let word: String = chars[start..idx].iter().collect();
+            let kind = match word.parse() {
+                Ok(number) => TokenKind::Number(number),
+                Err(_) if word.chars().all(|c| c.is_ascii_digit()) => Err(ScriptError {
+                    column,
+                    kind: ScriptErrorKind::NumberTooLarge,
+                })?,
+                Err(_) => TokenKind::Word,
+            };
+            tokens.push(Token {
+                kind,
+                text: word,
+                column,
+            });
+        } else {
+            let rest: String = chars[idx..chars.len().min(idx + 2)].iter().collect();
+            let symbol = SYMBOLS
+                .into_iter()
+                .find(|symbol| rest.starts_with(symbol))
+                .ok_or(ScriptError {
+                    column,
+                    kind: ScriptErrorKind::UnexpectedCharacter(chars[idx]),
+                })?;
+            idx += symbol.len();
+            tokens.push(Token {
+                kind: TokenKind::Symbol(symbol),
+                text: symbol.to_string(),
+                column,
+            });
+        }
+    }
+
+    tokens.push(Token {
+        kind: TokenKind::End,
+        text: String::new(),
+        column: chars.len() + 1,
+    });
+    Ok(tokens)
+}
+
+/// A hand shape, giving the length of spades, hearts, diamonds and clubs, where `None`
+/// stands for any length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Shape {
+    lengths: [Option<usize>; 4],
+    any_order: bool,
+}
+
+impl Shape {
+    fn matches(&self, hand: &Hand) -> bool {
+        let distribution = hand.distribution();
+        if !self.any_order {
+            return self
+                .lengths
+                .iter()
+                .zip(distribution)
+                .all(|(length, held)| length.is_none_or(|length| length == held));
+        }
+
+        // In any order, each given length must be matched by a different suit.
+        let mut unmatched = distribution.to_vec();
+        self.lengths.iter().flatten().all(|length| {
+            match unmatched.iter().position(|held| held == length) {
+                Some(idx) => {
+                    unmatched.swap_remove(idx);
+                    true
+                }
+                None => false,
+            }
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Expr {
+    Number(i64),
+    Hcp(Seat),
+    Length(Seat, Suit),
+    Shape(Seat, Vec<Shape>),
+    HasCard(Seat, Card),
+    Not(Box<Expr>),
+    Negate(Box<Expr>),
+    Binary(&'static str, Box<Expr>, Box<Expr>),
+}
+
+impl Expr {
+    fn evaluate(&self, hands: &[Hand; 4]) -> i64 {
+        match self {
+            Self::Number(number) => *number,
+            Self::Hcp(seat) => hands[*seat as usize].hcp() as i64,
+            Self::Length(seat, suit) => hands[*seat as usize].suit(*suit).len() as i64,
+            Self::Shape(seat, shapes) => {
+                let hand = &hands[*seat as usize];
+                shapes.iter().any(|shape| shape.matches(hand)) as i64
+            }
+            Self::HasCard(seat, card) => hands[*seat as usize].contains(card) as i64,
+            Self::Not(expr) => (expr.evaluate(hands) == 0) as i64,
+            Self::Negate(expr) => expr.evaluate(hands).wrapping_neg(),
+            // Logical operators short-circuit, as in Dealer.
+            Self::Binary("&&", lhs, rhs) => {
+                (lhs.evaluate(hands) != 0 && rhs.evaluate(hands) != 0) as i64
+            }
+            Self::Binary("||", lhs, rhs) => {
+                (lhs.evaluate(hands) != 0 || rhs.evaluate(hands) != 0) as i64
+            }
+            Self::Binary(op, lhs, rhs) => {
+                let (lhs, rhs) = (lhs.evaluate(hands), rhs.evaluate(hands));
+                match *op {
+                    "==" => (lhs == rhs) as i64,
+                    "!=" => (lhs != rhs) as i64,
+                    "<" => (lhs < rhs) as i64,
+                    "<=" => (lhs <= rhs) as i64,
+                    ">" => (lhs > rhs) as i64,
+                    ">=" => (lhs >= rhs) as i64,
+                    "+" => lhs.wrapping_add(rhs),
+                    "-" => lhs.wrapping_sub(rhs),
+                    "*" => lhs.wrapping_mul(rhs),
+                    "/" => lhs.checked_div(rhs).unwrap_or(0),
+                    "%" => lhs.checked_rem(rhs).unwrap_or(0),
+                    _ => unreachable!("the parser only builds known operators"),
+                }
+            }
+        }
+    }
+}
+
+/// The operators of each precedence level, from the loosest binding to the tightest.
+const LEVELS: [&[&str]; 5] = [
+    &["||"],
+    &["&&"],
+    &["==", "!=", "<=", ">=", "<", ">"],
+    &["+", "-"],
+    &["*", "/", "%"],
+];
+
+struct Parser {
+    tokens: Vec<Token>,
+    position: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> &Token {
+        &self.tokens[self.position]
+    }
+
+    fn next(&mut self) -> Token {
+        let token = self.tokens[self.position].clone();
+        if token.kind != TokenKind::End {
+            self.position += 1;
+        }
+        token
+    }
+
+    fn error(token: &Token, kind: ScriptErrorKind) -> ScriptError {
+        ScriptError {
+            column: token.column,
+            kind,
+        }
+    }
+
+    fn unexpected(token: &Token) -> ScriptError {
+        let kind = match token.kind {
+            TokenKind::End => ScriptErrorKind::UnexpectedEnd,
+            _ => ScriptErrorKind::UnexpectedToken,
+        };
+        Self::error(token, kind)
+    }
+
+    fn expect(&mut self, symbol: &'static str) -> Result<(), ScriptError> {
+        let token = self.next();
+        match token.kind {
+            TokenKind::Symbol(found) if found == symbol => Ok(()),
+            _ => Err(Self::unexpected(&token)),
+        }
+    }
+
+    fn word(&mut self) -> Result<Token, ScriptError> {
+        let token = self.next();
+        match token.kind {
+            TokenKind::Word => Ok(token),
+            _ => Err(Self::unexpected(&token)),
+        }
+    }
+
+    fn binary(&mut self, level: usize) -> Result<Expr, ScriptError> {
+        if level == LEVELS.len() {
+            return self.unary();
+        }
+
+        let mut expr = self.binary(level + 1)?;
+        while let TokenKind::Symbol(op) = self.peek().kind {
+            if !LEVELS[level].contains(&op) {
+                break;
+            }
+            self.next();
+            let rhs = self.binary(level + 1)?;
+            expr = Expr::Binary(op, Box::new(expr), Box::new(rhs));
+        }
+        Ok(expr)
+    }
+
+    fn unary(&mut self) -> Result<Expr, ScriptError> {
+        let token = self.next();
+        match token.kind {
+            TokenKind::Number(number) => Ok(Expr::Number(number)),
+            TokenKind::Symbol("!") => Ok(Expr::Not(Box::new(self.unary()?))),
+            TokenKind::Symbol("-") => Ok(Expr::Negate(Box::new(self.unary()?))),
+            TokenKind::Symbol("(") => {
+                let expr = self.binary(0)?;
+                self.expect(")")?;
+                Ok(expr)
+            }
+            TokenKind::Word => self.call(&token, &token.text.to_lowercase()),
+            _ => Err(Self::unexpected(&token)),
+        }
+    }
+
+    fn call(&mut self, token: &Token, function: &str) -> Result<Expr, ScriptError> {
+        const FUNCTIONS: [&str; 7] = [
+            "hcp", "spades", "hearts", "diamonds", "clubs", "shape", "hascard",
+        ];
+        if !FUNCTIONS.contains(&function) {
+            return Err(Self::error(token, ScriptErrorKind::UnknownFunction));
+        }
+
+        self.expect("(")?;
+        let seat = self.word()?;
+        let seat = Seat::from_str(&seat.text)
+            .map_err(|_| Self::error(&seat, ScriptErrorKind::SeatNotValid))?;
+
+        let expr = match function {
+            "hcp" => Expr::Hcp(seat),
+            "spades" => Expr::Length(seat, Suit::Spades),
+            "hearts" => Expr::Length(seat, Suit::Hearts),
+            "diamonds" => Expr::Length(seat, Suit::Diamonds),
+            "clubs" => Expr::Length(seat, Suit::Clubs),
+            "shape" => {
+                self.expect(",")?;
+                let mut shapes = vec![self.shape()?];
+                while self.peek().kind == TokenKind::Symbol("+") {
+                    self.next();
+                    shapes.push(self.shape()?);
+                }
+                Expr::Shape(seat, shapes)
+            }
+            "hascard" => {
+                self.expect(",")?;
+                let token = self.next();
+                let card = match token.kind {
+                    TokenKind::Word => token.text.to_uppercase().parse().ok(),
+                    _ => None,
+                };
+                let card = card.ok_or(Self::error(&token, ScriptErrorKind::CardNotValid))?;
+                Expr::HasCard(seat, card)
+            }
+            _ => unreachable!("the function name has been checked"),
+        };
+
+        self.expect(")")?;
+        Ok(expr)
+    }
+
+    fn shape(&mut self) -> Result<Shape, ScriptError> {
+        let mut token = self.next();
+        let any_order = token.kind == TokenKind::Word && token.text.to_lowercase() == "any";
+        if any_order {
+            token = self.next();
+        }
+
+        let pattern = match &token.kind {
+            TokenKind::Number(_) | TokenKind::Word => token.text.to_lowercase(),
+            _ => return Err(Self::unexpected(&token)),
+        };
+        let lengths: Vec<_> = pattern
+            .chars()
+            .map(|c| match c {
+                'x' => Some(None),
+                _ => c.to_digit(10).map(|length| Some(length as usize)),
+            })
+            .collect::<Option<_>>()
+            .ok_or(Self::error(&token, ScriptErrorKind::ShapeNotValid))?;
+        let lengths: [Option<usize>; 4] = lengths
+            .try_into()
+            .map_err(|_| Self::error(&token, ScriptErrorKind::ShapeNotValid))?;
+
+        let total: usize = lengths.iter().flatten().sum();
+        let complete = lengths.iter().all(Option::is_some);
+        if total > 13 || (complete && total != 13) {
+            return Err(Self::error(&token, ScriptErrorKind::ShapeNotValid));
+        }
+
+        Ok(Shape { lengths, any_order })
+    }
+}
+
+/// A parsed constraint script, which can be evaluated against a deal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Script(Expr);
+
+impl FromStr for Script {
+    type Err = ScriptError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            tokens: tokenize(s)?,
+            position: 0,
+        };
+        let expr = parser.binary(0)?;
+        match parser.peek() {
+            Token {
+                kind: TokenKind::End,
+                ..
+            } => Ok(Self(expr)),
+            token => Err(Parser::unexpected(token)),
+        }
+    }
+}
+
+impl Script {
+    /// Returns the value of the script for the given hands, indexed by seat.
+    pub fn evaluate(&self, hands: &[Hand; 4]) -> i64 {
+        self.0.evaluate(hands)
+    }
+
+    /// Returns whether the given hands, indexed by seat, satisfy the script.
+    pub fn matches(&self, hands: &[Hand; 4]) -> bool {
+        self.evaluate(hands) != 0
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn hands() -> [Hand; 4] {
+        let hand = |cards: &[&str]| {
+            Hand::from(
+                cards
+                    .iter()
+                    .map(|card| card.parse::<Card>().unwrap())
+                    .collect::<Vec<_>>(),
+            )
+        };
+        [
+            hand(&[
+                "AS", "KS", "QS", "JS", "2S", "3H", "4H", "5H", "6D", "7D", "8D", "9C", "TC",
+            ]),
+            hand(&[
+                "3S", "4S", "5S", "AH", "KH", "QH", "JH", "2H", "6H", "AD", "KD", "QD", "JD",
+            ]),
+            hand(&[
+                "6S", "7S", "8S", "7H", "8H", "9H", "2D", "3D", "4D", "AC", "KC", "QC", "JC",
+            ]),
+            hand(&[
+                "9S", "TS", "TH", "5D", "9D", "TD", "2C", "3C", "4C", "5C", "6C", "7C", "8C",
+            ]),
+        ]
+    }
+
+    fn evaluate(script: &str) -> i64 {
+        script.parse::<Script>().unwrap().evaluate(&hands())
+    }
+
+    #[test]
+    fn functions() {
+        assert_eq!(10, evaluate("hcp(north)"));
+        assert_eq!(5, evaluate("spades(north)"));
+        assert_eq!(6, evaluate("HEARTS(East)"));
+        assert_eq!(1, evaluate("hascard(south, AC)"));
+        assert_eq!(0, evaluate("hascard(south, tc)"));
+        assert_eq!(1, evaluate("shape(north, 5332)"));
+        assert_eq!(0, evaluate("shape(north, 3532)"));
+        assert_eq!(1, evaluate("shape(south, any 4333 + any 4432)"));
+        assert_eq!(1, evaluate("shape(west, 2x3x)"));
+        assert_eq!(1, evaluate("shape(west, any x7xx)"));
+        assert_eq!(0, evaluate("shape(west, any 5xxx)"));
+    }
+
+    #[test]
+    fn operators() {
+        assert_eq!(7, evaluate("1 + 2 * 3"));
+        assert_eq!(9, evaluate("(1 + 2) * 3"));
+        assert_eq!(-1, evaluate("2 - 3"));
+        assert_eq!(0, evaluate("7 / 0"));
+        assert_eq!(1, evaluate("7 % 3 == 1 && !(2 > 3) || 0"));
+        assert_eq!(
+            1,
+            evaluate(
+                "hcp(north) >= 10 && hcp(north) <= 12 && shape(north, any 4333 + any 4432 + any \
+                 5332) && spades(north) >= 4"
+            )
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let error = |script: &str| script.parse::<Script>().unwrap_err();
+        assert_eq!(
+            ScriptError {
+                column: 14,
+                kind: ScriptErrorKind::UnexpectedCharacter('$'),
+            },
+            error("hcp(south) > $")
+        );
+        assert_eq!(
+            ScriptError {
+                column: 5,
+                kind: ScriptErrorKind::SeatNotValid,
+            },
+            error("hcp(sud) > 3")
+        );
+        assert_eq!(
+            ScriptError {
+                column: 1,
+                kind: ScriptErrorKind::UnknownFunction,
+            },
+            error("points(south)")
+        );
+        assert_eq!(
+            ScriptError {
+                column: 21,
+                kind: ScriptErrorKind::ShapeNotValid,
+            },
+            error("shape(south, 4432 + 4333x)")
+        );
+        assert_eq!(
+            ScriptError {
+                column: 13,
+                kind: ScriptErrorKind::UnexpectedEnd,
+            },
+            error("hcp(south) >")
+        );
+        assert_eq!(
+            ScriptError {
+                column: 12,
+                kind: ScriptErrorKind::UnexpectedToken,
+            },
+            error("hcp(south) 3")
+        );
+    }
+}
@@ -0,0 +1,698 @@
+//! Scoring a duplicate pairs session: travellers of the results on each board, and a
+//! ranking of the pairs by matchpoints or IMPs.
+
+use std::{collections::BTreeMap, fmt::Display};
+
+use crate::{
+    auction::{Contract, Side, Status},
+    board::Board,
+    error::SessionError,
+    movement::Movement,
+    scoring::{butler, cross_imps, neuberg},
+};
+
+/// Scores within this of each other are ranked equal.
+const TIE: f64 = 1e-6;
+
+/// How the results on a board are compared with each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Scoring {
+    /// Matchpoints, ranked by the percentage of the available matchpoints each pair won.
+    #[default]
+    Matchpoints,
+    /// IMPs against every other result, averaged, and ranked by the total.
+    CrossImps,
+    /// IMPs against the board's datum, and ranked by the total.
+    Butler,
+}
+
+/// The result of a board at one table. A passed out board has no contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BoardResult {
+    pub north_south: u32,
+    pub east_west: u32,
+    pub contract: Option<Contract>,
+    /// The tricks taken by declarer.
+    pub tricks: u8,
+}
+
+impl BoardResult {
+    /// Returns the score of the result from North-South's point of view.
+    pub fn score(&self, board: &Board) -> i32 {
+        let Some(contract) = self.contract else {
+            return 0;
+        };
+        let side = contract.declarer.side();
+        let score = contract.score(board.vulnerability.is_vulnerable(side), self.tricks);
+        match side {
+            Side::NorthSouth => score,
+            Side::EastWest => -score,
+        }
+    }
+}
+
+/// The results of a pairs session, scored board by board.
+#[derive(Debug, Clone, Default)]
+pub struct Session {
+    scoring: Scoring,
+    movement: Option<Movement>,
+    boards: BTreeMap<u32, (Board, Vec<BoardResult>)>,
+}
+
+impl Session {
+    pub fn new(scoring: Scoring) -> Self {
+        Self {
+            scoring,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the movement the session is played to, so that results for pairs who
+    /// shouldn't have met on a board, or sat the other way round, are refused.
+    pub fn movement(mut self, movement: Movement) -> Self {
+        self.movement = Some(movement);
+        self
+    }
+
+    pub fn scoring(&self) -> Scoring {
+        self.scoring
+    }
+
+    /// Records the result of a board at one table.
+    pub fn record(&mut self, board: Board, result: BoardResult) -> Result<(), SessionError> {
+        if result.north_south == result.east_west {
+            return Err(SessionError::PairsNotValid);
+        }
+        if result.tricks > 13 {
+            return Err(SessionError::TricksNotValid);
+        }
+        if let Some(movement) = &self.movement {
+            let sat = movement
+                .rounds()
+                .iter()
+                .flat_map(|round| &round.tables)
+                .any(|sitting| {
+                    sitting.north_south == result.north_south
+                        && sitting.east_west == result.east_west
+                        && sitting.boards.contains(&board.number)
+                });
+            if !sat {
+                return Err(SessionError::NotInMovement);
+            }
+        }
+
+        let (_, results) = self
+            .boards
+            .entry(board.number)
+            .or_insert_with(|| (board, vec![]));
+        let pairs = [result.north_south, result.east_west];
+        if results
+            .iter()
+            .any(|other| pairs.contains(&other.north_south) || pairs.contains(&other.east_west))
+        {
+            return Err(SessionError::ResultDuplicated);
+        }
+        results.push(result);
+        Ok(())
+    }
+
+    /// Returns the numbers of the boards with results, in order.
+    pub fn boards(&self) -> impl Iterator<Item = u32> + '_ {
+        self.boards.keys().copied()
+    }
+
+    pub fn results(&self, board: u32) -> &[BoardResult] {
+        self.boards
+            .get(&board)
+            .map_or(&[], |(_, results)| results.as_slice())
+    }
+
+    /// Returns the most times any board was played, which is the number of times a board
+    /// is expected to have been played when matchpointing.
+    fn expected_results(&self) -> usize {
+        self.boards
+            .values()
+            .map(|(_, results)| results.len())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Returns the matchpoints or IMPs won by each side for each result on a board, in
+    /// the order they were recorded. Matchpoints on boards played fewer times than the
+    /// rest are scaled up by Neuberg's formula.
+    fn points(&self, board: &Board, results: &[BoardResult]) -> Vec<(f64, f64)> {
+        let scores: Vec<_> = results.iter().map(|result| result.score(board)).collect();
+        match self.scoring {
+            Scoring::Matchpoints => {
+                let expected = self.expected_results();
+                let top = expected.saturating_sub(1) as f64;
+                neuberg(&scores, expected)
+                    .into_iter()
+                    .map(|points| (points, top - points))
+                    .collect()
+            }
+            Scoring::CrossImps => cross_imps(&scores)
+                .into_iter()
+                .map(|imps| (imps, 0.0 - imps))
+                .collect(),
+            Scoring::Butler => butler(&scores)
+                .into_iter()
+                .map(|imps| (imps as f64, -imps as f64))
+                .collect(),
+        }
+    }
+
+    /// Returns the traveller of a board, listing every result on it, or `None` if none
+    /// have been recorded.
+    pub fn traveller(&self, board: u32) -> Option<Traveller> {
+        let (board, results) = self.boards.get(&board)?;
+        Some(Traveller {
+            board: *board,
+            scoring: self.scoring,
+            rows: results
+                .iter()
+                .zip(self.points(board, results))
+                .map(|(result, points)| (*result, result.score(board), points))
+                .collect(),
+        })
+    }
+
+    /// Returns the standings of the pairs, best first. Pairs with equal scores share a
+    /// rank. In a movement in which no pair changes direction, such as a Mitchell, the
+    /// pairs only compete with those sitting the same way, so each direction is ranked
+    /// separately, North-South first.
+    pub fn ranking(&self) -> Ranking {
+        // Each pair's points and the points available to it.
+        let mut totals: BTreeMap<u32, (f64, f64, usize)> = BTreeMap::new();
+        let top = self.expected_results().saturating_sub(1) as f64;
+        for (board, results) in self.boards.values() {
+            for (result, (north_south, east_west)) in
+                results.iter().zip(self.points(board, results))
+            {
+                for (pair, points) in [
+                    (result.north_south, north_south),
+                    (result.east_west, east_west),
+                ] {
+                    let total = totals.entry(pair).or_default();
+                    total.0 += points;
+                    total.1 += top;
+                    total.2 += 1;
+                }
+            }
+        }
+
+        let directions = self.movement.as_ref().and_then(Movement::directions);
+        let mut standings: Vec<_> = totals
+            .into_iter()
+            .map(|(pair, (points, available, boards))| Standing {
+                rank: 0,
+                tied: false,
+                pair,
+                direction: directions
+                    .as_ref()
+                    .and_then(|directions| directions.get(&pair).copied()),
+                score: match self.scoring {
+                    Scoring::Matchpoints if available > 0.0 => 100.0 * points / available,
+                    Scoring::Matchpoints => 50.0,
+                    Scoring::CrossImps | Scoring::Butler => points,
+                },
+                boards,
+            })
+            .collect();
+        let direction = |standing: &Standing| standing.direction.map(|side| side as u8);
+        standings.sort_by(|lhs, rhs| {
+            direction(lhs)
+                .cmp(&direction(rhs))
+                .then(rhs.score.total_cmp(&lhs.score))
+                .then(lhs.pair.cmp(&rhs.pair))
+        });
+
+        let scores: Vec<_> = standings
+            .iter()
+            .map(|standing| (standing.direction, standing.score))
+            .collect();
+        for standing in &mut standings {
+            let rivals = scores
+                .iter()
+                .filter(|(direction, _)| *direction == standing.direction)
+                .map(|(_, score)| *score);
+            standing.rank = 1 + rivals
+                .clone()
+                .filter(|score| *score - standing.score > TIE)
+                .count();
+            standing.tied = rivals
+                .filter(|score| (*score - standing.score).abs() <= TIE)
+                .count()
+                > 1;
+        }
+
+        Ranking {
+            scoring: self.scoring,
+            standings,
+        }
+    }
+}
+
+/// Returns the contract without its declarer, such as "4♥X", and the declarer, or
+/// "Pass" and nothing for a passed out board.
+fn contract_cells(contract: Option<Contract>) -> (String, String) {
+    match contract {
+        Some(contract) => {
+            let status = match contract.status {
+                Status::Undoubled => "",
+                Status::Doubled => "X",
+                Status::Redoubled => "XX",
+            };
+            (
+                format!("{}{}", contract.bid, status),
+                char::from(contract.declarer).to_string(),
+            )
+        }
+        None => ("Pass".to_string(), String::new()),
+    }
+}
+
+/// Writes points to two decimal places, with a sign for IMPs.
+fn points_cell(scoring: Scoring, points: f64) -> String {
+    match scoring {
+        Scoring::Matchpoints => format!("{:.2}", points),
+        Scoring::CrossImps | Scoring::Butler => format!("{:+.2}", points),
+    }
+}
+
+fn direction_name(side: Side) -> &'static str {
+    match side {
+        Side::NorthSouth => "North-South",
+        Side::EastWest => "East-West",
+    }
+}
+
+fn points_name(scoring: Scoring) -> &'static str {
+    match scoring {
+        Scoring::Matchpoints => "MPs",
+        Scoring::CrossImps | Scoring::Butler => "IMPs",
+    }
+}
+
+/// The results of a board, each with its score from North-South's point of view and the
+/// points won by North-South and East-West.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Traveller {
+    pub board: Board,
+    pub scoring: Scoring,
+    pub rows: Vec<(BoardResult, i32, (f64, f64))>,
+}
+
+impl Traveller {
+    /// Returns the traveller as comma-separated values, with a header row.
+    pub fn to_csv(&self) -> String {
+        let mut lines = vec![
+            "board,north_south,east_west,contract,declarer,tricks,north_south_score,\
+             north_south_points,east_west_points"
+                .to_string(),
+        ];
+        for (result, score, (north_south, east_west)) in &self.rows {
+            let (contract, declarer) = contract_cells(result.contract);
+            lines.push(format!(
+                "{},{},{},{},{},{},{},{:.2},{:.2}",
+                self.board.number,
+                result.north_south,
+                result.east_west,
+                contract,
+                declarer,
+                result.tricks,
+                score,
+                north_south,
+                east_west
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+impl Display for Traveller {
+    /// Writes a row for each result, with the score in the column of the side which
+    /// scored it.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Board {}, dealer {}, vulnerable {}",
+            self.board.number,
+            char::from(self.board.dealer),
+            self.board.vulnerability
+        )?;
+        let points = points_name(self.scoring);
+        write!(
+            f,
+            "{:>4}{:>4}  {:<10}{:<4}{:>6}{:>8}{:>8}{:>10}{:>10}",
+            "NS",
+            "EW",
+            "Contract",
+            "By",
+            "Tricks",
+            "NS",
+            "EW",
+            format!("NS {}", points),
+            format!("EW {}", points)
+        )?;
+        for (result, score, (north_south, east_west)) in &self.rows {
+            let (contract, declarer) = contract_cells(result.contract);
+            let cell = |score: i32| match score > 0 {
+                true => score.to_string(),
+                false => String::new(),
+            };
+            write!(
+                f,
+                "\n{:>4}{:>4}  {:<10}{:<4}{:>6}{:>8}{:>8}{:>10}{:>10}",
+                result.north_south,
+                result.east_west,
+                contract,
+                declarer,
+                result.tricks,
+                cell(*score),
+                cell(-score),
+                points_cell(self.scoring, *north_south),
+                points_cell(self.scoring, *east_west)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// A pair's place in the ranking: a percentage of the matchpoints available to it, or
+/// its total IMPs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Standing {
+    pub rank: usize,
+    /// Whether another pair shares the rank.
+    pub tied: bool,
+    pub pair: u32,
+    /// The direction the pair sat, if the ranking is by direction.
+    pub direction: Option<Side>,
+    pub score: f64,
+    /// The number of boards the pair played.
+    pub boards: usize,
+}
+
+impl Standing {
+    /// Writes the rank, such as "3" or "3=" when tied.
+    fn rank_cell(&self) -> String {
+        match self.tied {
+            true => format!("{}=", self.rank),
+            false => self.rank.to_string(),
+        }
+    }
+}
+
+/// The standings of every pair in a session, best first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ranking {
+    pub scoring: Scoring,
+    pub standings: Vec<Standing>,
+}
+
+impl Ranking {
+    /// Returns whether each direction is ranked separately.
+    pub fn by_direction(&self) -> bool {
+        self.standings
+            .iter()
+            .any(|standing| standing.direction.is_some())
+    }
+
+    fn score_cell(&self, standing: &Standing) -> String {
+        match self.scoring {
+            Scoring::Matchpoints => format!("{:.2}%", standing.score),
+            Scoring::CrossImps | Scoring::Butler => format!("{:+.2}", standing.score),
+        }
+    }
+
+    /// Returns the ranking as comma-separated values, with a header row. A ranking by
+    /// direction starts each row with the direction.
+    pub fn to_csv(&self) -> String {
+        let by_direction = self.by_direction();
+        let mut lines = vec![match by_direction {
+            true => "direction,rank,pair,score,boards".to_string(),
+            false => "rank,pair,score,boards".to_string(),
+        }];
+        for standing in &self.standings {
+            let direction = match standing.direction {
+                Some(Side::NorthSouth) => "north_south,",
+                Some(Side::EastWest) => "east_west,",
+                None => "",
+            };
+            lines.push(format!(
+                "{}{},{},{:.2},{}",
+                direction,
+                standing.rank_cell(),
+                standing.pair,
+                standing.score,
+                standing.boards
+            ));
+        }
+        lines.join("\n")
+    }
+}
+
+impl Display for Ranking {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let score = match self.scoring {
+            Scoring::Matchpoints => "Score",
+            Scoring::CrossImps | Scoring::Butler => "IMPs",
+        };
+        let header = format!("{:<6}{:>4}{:>10}{:>8}", "Rank", "Pair", score, "Boards");
+        if self.standings.is_empty() {
+            return f.write_str(&header);
+        }
+        // A ranking by direction is written as one table per direction, each under its
+        // name.
+        for (idx, group) in self
+            .standings
+            .chunk_by(|lhs, rhs| lhs.direction == rhs.direction)
+            .enumerate()
+        {
+            if idx > 0 {
+                f.write_str("\n\n")?;
+            }
+            if let Some(side) = group[0].direction {
+                writeln!(f, "{}", direction_name(side))?;
+            }
+            f.write_str(&header)?;
+            for standing in group {
+                write!(
+                    f,
+                    "\n{:<6}{:>4}{:>10}{:>8}",
+                    standing.rank_cell(),
+                    standing.pair,
+                    self.score_cell(standing),
+                    standing.boards
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::auction::ContractBid;
+
+    fn result(north_south: u32, east_west: u32, contract: &str, tricks: u8) -> BoardResult {
+        let contract = contract.split_once(' ').map(|(bid, declarer)| Contract {
+            bid: bid.parse::<ContractBid>().unwrap(),
+            status: Status::Undoubled,
+            declarer: declarer.parse::<char>().unwrap().try_into().unwrap(),
+        });
+        BoardResult {
+            north_south,
+            east_west,
+            contract,
+            tricks,
+        }
+    }
+
+    /// Three tables of a Mitchell, pairs 1-3 North-South against 4-6 East-West, on two
+    /// boards.
+    fn session(scoring: Scoring) -> Session {
+        let mut session = Session::new(scoring);
+        for result in [
+            result(1, 4, "4S N", 10),
+            result(2, 5, "4S N", 11),
+            result(3, 6, "3NT S", 8),
+        ] {
+            session.record(Board::new(1), result).unwrap();
+        }
+        for result in [
+            result(1, 5, "2H E", 8),
+            result(2, 6, "P", 0),
+            result(3, 4, "3C N", 9),
+        ] {
+            session.record(Board::new(2), result).unwrap();
+        }
+        session
+    }
+
+    #[test]
+    fn matchpoints() {
+        let session = session(Scoring::Matchpoints);
+        let traveller = session.traveller(1).unwrap();
+        assert_eq!(
+            vec![(420, (1.0, 1.0)), (450, (2.0, 0.0)), (-50, (0.0, 2.0))],
+            traveller
+                .rows
+                .iter()
+                .map(|(_, score, points)| (*score, *points))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![
+                "Board 1, dealer N, vulnerable None",
+                "  NS  EW  Contract  By  Tricks      NS      EW    NS MPs    EW MPs",
+                "   1   4  4♠        N       10     420              1.00      1.00",
+                "   2   5  4♠        N       11     450              2.00      0.00",
+                "   3   6  3NT       S        8              50      0.00      2.00",
+            ],
+            traveller.to_string().lines().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            "board,north_south,east_west,contract,declarer,tricks,north_south_score,\
+             north_south_points,east_west_points\n\
+             2,1,5,2♥,E,8,-110,0.00,2.00\n\
+             2,2,6,Pass,,0,0,1.00,1.00\n\
+             2,3,4,3♣,N,9,110,2.00,0.00",
+            session.traveller(2).unwrap().to_csv()
+        );
+        assert!(session.traveller(3).is_none());
+
+        let ranking = session.ranking();
+        assert_eq!(
+            vec![
+                "Rank  Pair     Score  Boards",
+                "1=       2    75.00%       2",
+                "1=       6    75.00%       2",
+                "3=       3    50.00%       2",
+                "3=       5    50.00%       2",
+                "5=       1    25.00%       2",
+                "5=       4    25.00%       2",
+            ],
+            ranking.to_string().lines().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn imps() {
+        let cross_imps = session(Scoring::CrossImps);
+        let traveller = cross_imps.traveller(1).unwrap();
+        // Down one at no trumps loses 10 IMPs against 420 and 11 against 450.
+        assert_eq!((-10.5, 10.5), traveller.rows[2].2);
+        assert_eq!(
+            "rank,pair,score,boards\n\
+             1,6,10.50,2\n\
+             2,2,6.00,2\n\
+             3,1,0.00,2\n\
+             4,5,-1.50,2\n\
+             5,3,-6.00,2\n\
+             6,4,-9.00,2",
+            cross_imps.ranking().to_csv()
+        );
+
+        // The datum leaves out the highest and lowest scores, so is 420 on board 1.
+        let butler = session(Scoring::Butler);
+        let traveller = butler.traveller(1).unwrap();
+        assert_eq!(
+            vec![(0.0, 0.0), (1.0, -1.0), (-10.0, 10.0)],
+            traveller.rows.iter().map(|row| row.2).collect::<Vec<_>>()
+        );
+        let standings: Vec<_> = butler
+            .ranking()
+            .standings
+            .into_iter()
+            .map(|standing| (standing.rank_cell(), standing.pair, standing.score))
+            .collect();
+        assert_eq!(("4=".to_string(), 1, -3.0), standings[3]);
+        assert_eq!(("4=".to_string(), 4, -3.0), standings[4]);
+    }
+
+    #[test]
+    fn two_winners() {
+        // No pair changes direction in a Mitchell, so each direction has its own winner.
+        let mut session =
+            Session::new(Scoring::Matchpoints).movement(Movement::mitchell(3, 1).unwrap());
+        for (board, result) in [
+            (1, result(1, 4, "4S N", 10)),
+            (1, result(2, 6, "4S N", 11)),
+            (1, result(3, 5, "3NT S", 8)),
+            (2, result(1, 6, "2H E", 8)),
+            (2, result(2, 5, "P", 0)),
+            (2, result(3, 4, "3C N", 9)),
+        ] {
+            session.record(Board::new(board), result).unwrap();
+        }
+
+        let ranking = session.ranking();
+        assert!(ranking.by_direction());
+        assert_eq!(
+            vec![
+                "North-South",
+                "Rank  Pair     Score  Boards",
+                "1        2    75.00%       2",
+                "2        3    50.00%       2",
+                "3        1    25.00%       2",
+                "",
+                "East-West",
+                "Rank  Pair     Score  Boards",
+                "1        5    75.00%       2",
+                "2        6    50.00%       2",
+                "3        4    25.00%       2",
+            ],
+            ranking.to_string().lines().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            "direction,rank,pair,score,boards\n\
+             north_south,1,2,75.00,2\n\
+             north_south,2,3,50.00,2\n\
+             north_south,3,1,25.00,2\n\
+             east_west,1,5,75.00,2\n\
+             east_west,2,6,50.00,2\n\
+             east_west,3,4,25.00,2",
+            ranking.to_csv()
+        );
+
+        // After an arrow switch, every pair competes with every other.
+        let session = Session::new(Scoring::Matchpoints)
+            .movement(Movement::mitchell(3, 1).unwrap().arrow_switch(3));
+        assert!(!session.ranking().by_direction());
+    }
+
+    #[test]
+    fn errors() {
+        let mut session = session(Scoring::Matchpoints);
+        assert_eq!(
+            Err(SessionError::ResultDuplicated),
+            session.record(Board::new(1), result(4, 7, "4S N", 10))
+        );
+        assert_eq!(
+            Err(SessionError::PairsNotValid),
+            session.record(Board::new(3), result(1, 1, "4S N", 10))
+        );
+        assert_eq!(
+            Err(SessionError::TricksNotValid),
+            session.record(Board::new(3), result(1, 4, "4S N", 14))
+        );
+
+        let mut session =
+            Session::new(Scoring::Matchpoints).movement(Movement::mitchell(3, 2).unwrap());
+        assert_eq!(
+            Ok(()),
+            session.record(Board::new(2), result(1, 4, "4S N", 10))
+        );
+        assert_eq!(
+            Err(SessionError::NotInMovement),
+            session.record(Board::new(3), result(1, 4, "4S N", 10))
+        );
+        assert_eq!(
+            Err(SessionError::NotInMovement),
+            session.record(Board::new(3), result(4, 1, "4S N", 10))
+        );
+    }
+}
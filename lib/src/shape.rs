@@ -0,0 +1,206 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{card::Suit, error::ParseError, hand::Hand};
+
+/// The order of the lengths in a [`Shape`].
+const SUITS: [Suit; 4] = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs];
+
+/// A hand pattern: the lengths of its suits from longest to shortest, whichever suits they
+/// are, as in "4-4-4-1".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Pattern([usize; 4]);
+
+impl Pattern {
+    pub fn lengths(&self) -> [usize; 4] {
+        self.0
+    }
+}
+
+impl Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let [a, b, c, d] = self.0;
+        write!(f, "{a}-{b}-{c}-{d}")
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = ParseError;
+
+    /// Parses four lengths adding up to 13, in any order and optionally separated by
+    /// dashes, such as "4441" or "5-3-3-2".
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let lengths: Vec<usize> = s
+            .chars()
+            .filter(|c| *c != '-')
+            .map(|c| c.to_digit(10).map(|length| length as usize))
+            .collect::<Option<_>>()
+            .ok_or(ParseError::ShapeNotValid)?;
+        let mut lengths: [usize; 4] = lengths.try_into().map_err(|_| ParseError::ShapeNotValid)?;
+        if lengths.iter().sum::<usize>() != 13 {
+            return Err(ParseError::ShapeNotValid);
+        }
+
+        lengths.sort_by(|a, b| b.cmp(a));
+        Ok(Self(lengths))
+    }
+}
+
+/// A broad class of hand shapes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShapeClass {
+    /// 4-3-3-3, 4-4-3-2 or 5-3-3-2.
+    Balanced,
+    /// Any other shape without a singleton or void, such as 5-4-2-2 or 6-3-2-2.
+    SemiBalanced,
+    /// One long suit, such as 6-3-3-1 or 7-3-2-1.
+    SingleSuited,
+    /// Two suits of four or more cards, such as 5-4-3-1 or 6-5-1-1.
+    TwoSuited,
+    /// Three suits of four or more cards: 4-4-4-1 or 5-4-4-0.
+    ThreeSuited,
+}
+
+impl Display for ShapeClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Balanced => "balanced",
+            Self::SemiBalanced => "semi-balanced",
+            Self::SingleSuited => "single-suited",
+            Self::TwoSuited => "two-suited",
+            Self::ThreeSuited => "three-suited",
+        })
+    }
+}
+
+/// The exact shape of a hand: its lengths in spades, hearts, diamonds and clubs, as in
+/// "5=4=3=1".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Shape([usize; 4]);
+
+impl Shape {
+    /// Returns the lengths of spades, hearts, diamonds and clubs, in that order.
+    pub fn lengths(&self) -> [usize; 4] {
+        self.0
+    }
+
+    pub fn length(&self, suit: Suit) -> usize {
+        self.0[SUITS.iter().position(|other| *other == suit).unwrap()]
+    }
+
+    pub fn pattern(&self) -> Pattern {
+        let mut lengths = self.0;
+        lengths.sort_by(|a, b| b.cmp(a));
+        Pattern(lengths)
+    }
+
+    pub fn class(&self) -> ShapeClass {
+        match self.pattern().0 {
+            [4, 3, 3, 3] | [4, 4, 3, 2] | [5, 3, 3, 2] => ShapeClass::Balanced,
+            [.., shortest] if shortest >= 2 => ShapeClass::SemiBalanced,
+            [_, _, third, _] if third >= 4 => ShapeClass::ThreeSuited,
+            [_, second, ..] if second >= 4 => ShapeClass::TwoSuited,
+            _ => ShapeClass::SingleSuited,
+        }
+    }
+
+    pub fn is_balanced(&self) -> bool {
+        self.class() == ShapeClass::Balanced
+    }
+
+    pub fn is_semi_balanced(&self) -> bool {
+        self.class() == ShapeClass::SemiBalanced
+    }
+
+    /// Returns the longest suits, from spades down.
+    pub fn longest(&self) -> Vec<Suit> {
+        let longest = self.pattern().0[0];
+        self.suits_of_length(longest)
+    }
+
+    /// Returns the shortest suits, from spades down.
+    pub fn shortest(&self) -> Vec<Suit> {
+        let shortest = self.pattern().0[3];
+        self.suits_of_length(shortest)
+    }
+
+    fn suits_of_length(&self, length: usize) -> Vec<Suit> {
+        SUITS
+            .into_iter()
+            .zip(self.0)
+            .filter(|(_, other)| *other == length)
+            .map(|(suit, _)| suit)
+            .collect()
+    }
+}
+
+impl Display for Shape {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let [spades, hearts, diamonds, clubs] = self.0;
+        write!(f, "{spades}={hearts}={diamonds}={clubs}")
+    }
+}
+
+impl Hand {
+    pub fn shape(&self) -> Shape {
+        Shape(self.distribution())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::card::Card;
+
+    fn shape(lengths: [usize; 4]) -> Shape {
+        Shape(lengths)
+    }
+
+    #[test]
+    fn patterns() {
+        let five_four = shape([1, 5, 3, 4]);
+        assert_eq!("1=5=3=4", five_four.to_string());
+        assert_eq!("5-4-3-1", five_four.pattern().to_string());
+        assert_eq!(Ok(five_four.pattern()), "5431".parse());
+        assert_eq!(Ok(five_four.pattern()), "1-3-4-5".parse());
+        assert_eq!(5, five_four.length(Suit::Hearts));
+        assert_eq!(Err(ParseError::ShapeNotValid), "5432".parse::<Pattern>());
+        assert_eq!(Err(ParseError::ShapeNotValid), "544".parse::<Pattern>());
+    }
+
+    #[test]
+    fn classes() {
+        assert_eq!(ShapeClass::Balanced, shape([3, 3, 4, 3]).class());
+        assert_eq!(ShapeClass::Balanced, shape([2, 5, 3, 3]).class());
+        assert_eq!(ShapeClass::SemiBalanced, shape([5, 4, 2, 2]).class());
+        assert_eq!(ShapeClass::SemiBalanced, shape([2, 2, 3, 6]).class());
+        assert_eq!(ShapeClass::SingleSuited, shape([7, 3, 2, 1]).class());
+        assert_eq!(ShapeClass::SingleSuited, shape([1, 3, 3, 6]).class());
+        assert_eq!(ShapeClass::TwoSuited, shape([5, 4, 3, 1]).class());
+        assert_eq!(ShapeClass::TwoSuited, shape([0, 6, 5, 2]).class());
+        assert_eq!(ShapeClass::ThreeSuited, shape([4, 4, 1, 4]).class());
+        assert_eq!(ShapeClass::ThreeSuited, shape([0, 4, 5, 4]).class());
+    }
+
+    #[test]
+    fn longest_and_shortest() {
+        assert_eq!(vec![Suit::Hearts], shape([1, 5, 3, 4]).longest());
+        assert_eq!(vec![Suit::Spades], shape([1, 5, 3, 4]).shortest());
+        assert_eq!(
+            vec![Suit::Spades, Suit::Hearts, Suit::Clubs],
+            shape([4, 4, 1, 4]).longest()
+        );
+    }
+
+    #[test]
+    fn hand_shape() {
+        let hand = Hand::from(
+            [
+                "AS", "KS", "QS", "JS", "TS", "AH", "KH", "QH", "JH", "AD", "KD", "QD", "AC",
+            ]
+            .iter()
+            .map(|card| card.parse::<Card>().unwrap())
+            .collect::<Vec<_>>(),
+        );
+        assert_eq!(shape([5, 4, 3, 1]), hand.shape());
+    }
+}
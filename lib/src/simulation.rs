@@ -0,0 +1,339 @@
+//! Monte Carlo simulation of contracts: dealing the unknown cards many times and solving
+//! each deal double-dummy, to compare how often contracts make and what they score.
+
+use std::{fmt::Display, num::NonZeroUsize};
+
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::{
+    auction::{Contract, Seat},
+    board::Vulnerability,
+    dds,
+    error::DealError,
+    generator::DealGenerator,
+    hand::Hand,
+    scoring::{imps, matchpoints},
+};
+
+/// A simulation of contracts over random deals from a generator, which fixes the hands
+/// that are known and constrains the others, for example with
+/// [`Shown::admits`](crate::inference::Shown::admits) to agree with the auction.
+pub struct Simulation {
+    generator: DealGenerator,
+    contracts: Vec<Contract>,
+    vulnerability: Vulnerability,
+    deals: usize,
+    threads: usize,
+    seed: Option<u64>,
+}
+
+impl Simulation {
+    pub fn new(generator: DealGenerator) -> Self {
+        Self {
+            generator,
+            contracts: vec![],
+            vulnerability: Vulnerability::None,
+            deals: 1000,
+            threads: std::thread::available_parallelism().map_or(1, NonZeroUsize::get),
+            seed: None,
+        }
+    }
+
+    /// Adds a contract to compare with those added before.
+    pub fn contract(mut self, contract: Contract) -> Self {
+        self.contracts.push(contract);
+        self
+    }
+
+    /// Sets the vulnerability the contracts are scored at. Defaults to neither side.
+    pub fn vulnerability(mut self, vulnerability: Vulnerability) -> Self {
+        self.vulnerability = vulnerability;
+        self
+    }
+
+    /// Sets the number of deals to simulate. Defaults to a thousand.
+    pub fn deals(mut self, deals: usize) -> Self {
+        self.deals = deals;
+        self
+    }
+
+    /// Sets the number of threads to deal and solve on. Defaults to the number of
+    /// processors available.
+    pub fn threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Seeds the deals, so that the same seed, deals and threads always give the same
+    /// report.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Deals and solves every deal, returning an error if the generator can't deal one.
+    pub fn run(&self) -> Result<Report, DealError> {
+        let seed = self.seed.unwrap_or_else(rand::random);
+        let tricks = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..self.threads)
+                .map(|thread| {
+                    let deals =
+                        self.deals / self.threads + (thread < self.deals % self.threads) as usize;
+                    let mut rng = StdRng::seed_from_u64(seed.wrapping_add(thread as u64));
+                    scope.spawn(move || {
+                        (0..deals)
+                            .map(
+                                |_| Ok(self.solve(&self.generator.generate_with(&mut rng)?.into())),
+                            )
+                            .collect::<Result<Vec<_>, DealError>>()
+                    })
+                })
+                .collect();
+
+            let mut tricks = vec![];
+            for handle in handles {
+                tricks.extend(handle.join().unwrap()?);
+            }
+            Ok(tricks)
+        })?;
+
+        Ok(Report {
+            contracts: self.contracts.clone(),
+            vulnerability: self.vulnerability,
+            tricks,
+        })
+    }
+
+    /// Returns the tricks declarer takes in each contract, solving each strain and
+    /// declarer only once.
+    fn solve(&self, hands: &[Hand; 4]) -> Vec<u8> {
+        let mut solved: Vec<(Contract, u8)> = vec![];
+        self.contracts
+            .iter()
+            .map(|contract| {
+                let same = |other: &&(Contract, u8)| {
+                    other.0.bid.suit == contract.bid.suit && other.0.declarer == contract.declarer
+                };
+                if let Some((_, tricks)) = solved.iter().find(same) {
+                    return *tricks;
+                }
+                let tricks = dds::solve(hands, contract.bid.suit, contract.declarer + 1)
+                    [contract.declarer.side() as usize];
+                solved.push((*contract, tricks));
+                tricks
+            })
+            .collect()
+    }
+}
+
+/// The results of a [`Simulation`]. Contracts are referred to by the order they were
+/// added in.
+#[derive(Debug, Clone)]
+pub struct Report {
+    contracts: Vec<Contract>,
+    vulnerability: Vulnerability,
+    /// The tricks declarer takes in each contract, deal by deal.
+    tricks: Vec<Vec<u8>>,
+}
+
+impl Report {
+    pub fn contracts(&self) -> &[Contract] {
+        &self.contracts
+    }
+
+    pub fn deals(&self) -> usize {
+        self.tricks.len()
+    }
+
+    /// Returns the tricks declarer takes in the contract on each deal.
+    pub fn tricks(&self, contract: usize) -> impl Iterator<Item = u8> + '_ {
+        self.tricks.iter().map(move |tricks| tricks[contract])
+    }
+
+    /// Returns the scores of the contract on each deal, from the point of view of the
+    /// side `seat` sits on.
+    fn scores(&self, contract: usize, seat: Seat) -> impl Iterator<Item = i32> + '_ {
+        let declarer = self.contracts[contract].declarer.side();
+        let vulnerable = self.vulnerability.is_vulnerable(declarer);
+        self.tricks(contract).map(move |tricks| {
+            let score = self.contracts[contract].score(vulnerable, tricks);
+            match declarer == seat.side() {
+                true => score,
+                false => -score,
+            }
+        })
+    }
+
+    fn average(&self, total: f64) -> f64 {
+        match self.deals() {
+            0 => 0.0,
+            deals => total / deals as f64,
+        }
+    }
+
+    /// Returns the fraction of deals on which the contract makes.
+    pub fn make_probability(&self, contract: usize) -> f64 {
+        let target = self.contracts[contract].target();
+        let made = self
+            .tricks(contract)
+            .filter(|tricks| *tricks >= target)
+            .count();
+        self.average(made as f64)
+    }
+
+    pub fn average_tricks(&self, contract: usize) -> f64 {
+        self.average(self.tricks(contract).map(f64::from).sum())
+    }
+
+    /// Returns the average score of the contract, from the declaring side's point of view.
+    pub fn average_score(&self, contract: usize) -> f64 {
+        let declarer = self.contracts[contract].declarer;
+        self.average(self.scores(contract, declarer).map(f64::from).sum())
+    }
+
+    /// Returns the average number of IMPs the side declaring contract `lhs` gains by
+    /// playing it rather than `rhs`.
+    pub fn imp_gain(&self, lhs: usize, rhs: usize) -> f64 {
+        let seat = self.contracts[lhs].declarer;
+        let gains = self
+            .scores(lhs, seat)
+            .zip(self.scores(rhs, seat))
+            .map(|(lhs, rhs)| imps(lhs - rhs));
+        self.average(gains.map(f64::from).sum())
+    }
+
+    /// Returns the fraction of the matchpoints the side declaring contract `lhs` would
+    /// win against a table playing `rhs`: a whole point for each deal on which it scores
+    /// more, and half a point for each on which the scores tie.
+    pub fn matchpoint_gain(&self, lhs: usize, rhs: usize) -> f64 {
+        let seat = self.contracts[lhs].declarer;
+        let points = self
+            .scores(lhs, seat)
+            .zip(self.scores(rhs, seat))
+            .map(|(lhs, rhs)| matchpoints(&[lhs, rhs])[0]);
+        self.average(points.sum())
+    }
+}
+
+impl Display for Report {
+    /// Writes a row for each contract, followed by how each compares with the first.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} deals", self.deals())?;
+        writeln!(
+            f,
+            "{:<12}{:>8}{:>8}{:>8}",
+            "Contract", "Makes", "Tricks", "Score"
+        )?;
+        for (idx, contract) in self.contracts.iter().enumerate() {
+            writeln!(
+                f,
+                "{:<12}{:>7.1}%{:>8.2}{:>+8.0}",
+                contract.to_string(),
+                100.0 * self.make_probability(idx),
+                self.average_tricks(idx),
+                self.average_score(idx)
+            )?;
+        }
+        for (idx, contract) in self.contracts.iter().enumerate().skip(1) {
+            write!(
+                f,
+                "\n{} against {}: {:+.2} IMPs, {:.1}% of matchpoints",
+                contract,
+                self.contracts[0],
+                self.imp_gain(idx, 0),
+                100.0 * self.matchpoint_gain(idx, 0)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::auction::{ContractBid, Status};
+
+    fn contract(bid: &str, declarer: Seat) -> Contract {
+        Contract {
+            bid: bid.parse::<ContractBid>().unwrap(),
+            status: Status::Undoubled,
+            declarer,
+        }
+    }
+
+    #[test]
+    fn grand_slams() {
+        // North holds every spade and South every heart: the defenders can't take a
+        // trick against a grand slam in either suit, and take every trick at no trumps.
+        let generator = DealGenerator::new()
+            .hand(Seat::North, &"AKQJT98765432...".parse().unwrap())
+            .hand(Seat::South, &".AKQJT98765432..".parse().unwrap());
+        let report = Simulation::new(generator)
+            .contract(contract("7S", Seat::North))
+            .contract(contract("7H", Seat::South))
+            .contract(contract("7NT", Seat::North))
+            .deals(6)
+            .threads(4)
+            .seed(1)
+            .run()
+            .unwrap();
+
+        assert_eq!(6, report.deals());
+        assert_eq!(1.0, report.make_probability(0));
+        assert_eq!(13.0, report.average_tricks(1));
+        assert_eq!(0.0, report.make_probability(2));
+        assert_eq!(-650.0, report.average_score(2));
+        assert_eq!(0.0, report.imp_gain(1, 0));
+        assert_eq!(0.5, report.matchpoint_gain(1, 0));
+        assert_eq!(-19.0, report.imp_gain(2, 0));
+        assert_eq!(0.0, report.matchpoint_gain(2, 0));
+        assert!(report
+            .to_string()
+            .contains("7NT by N        0.0%    0.00    -650"));
+    }
+
+    #[test]
+    fn constrained_by_the_auction() {
+        // Only the spade ace and diamond two are unknown. South can run thirteen tricks
+        // at no trumps unless East holds the ace and leads it, which West's 1♠ opening
+        // rules out: without the ace, West would hold six HCP.
+        let system: crate::system::System = "-> 1S [hcp 10-21, spades 5+]".parse().unwrap();
+        let mut auction = crate::auction::Auction::new(Seat::West);
+        auction.enter_bid("1S".parse().unwrap()).unwrap();
+        let shown = system.inferences(&auction)[Seat::West as usize].clone();
+
+        let known = || {
+            DealGenerator::new()
+                .hand(Seat::North, &".AKQJT98765432..".parse().unwrap())
+                .hand(Seat::South, &"..AKQJT987.AKQJT".parse().unwrap())
+                .hand(Seat::West, &"KQJT98765432...".parse().unwrap())
+                .hand(Seat::East, &"..6543.98765432".parse().unwrap())
+        };
+        let simulation = |generator| {
+            Simulation::new(generator)
+                .contract(contract("7NT", Seat::North))
+                .deals(20)
+                .threads(2)
+                .seed(1)
+                .run()
+                .unwrap()
+        };
+
+        assert!(simulation(known()).make_probability(0) < 1.0);
+        let report = simulation(known().seat(Seat::West, move |hand| shown.admits(hand)));
+        assert_eq!(1.0, report.make_probability(0));
+        assert_eq!(13.0, report.average_tricks(0));
+    }
+
+    #[test]
+    fn errors() {
+        let generator = DealGenerator::new()
+            .seat(Seat::North, crate::generator::hcp(38..))
+            .attempts(10);
+        let simulation = Simulation::new(generator).contract(contract("3NT", Seat::North));
+        assert_eq!(
+            Err(DealError::AttemptsExhausted),
+            simulation.run().map(|_| ())
+        );
+    }
+}
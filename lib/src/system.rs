@@ -0,0 +1,408 @@
+//! Bidding systems described in a text format, so that each partnership can keep its own
+//! system in a file. Each line is a rule giving a call to make after a sequence of calls,
+//! if the hand satisfies some constraints, with an optional explanation:
+//!
+//! ```text
+//! # Openings
+//! -> 1NT [hcp 15-17, balanced] "15-17 points, balanced"
+//! -> 1S [hcp 12-21, spades 5+] "five or more spades"
+//!
+//! # Responses to 1NT
+//! 1NT P -> 2C [hcp 8+, not balanced, forcing] "Stayman, asking for a four-card major"
+//! 1NT * -> Pass
+//! ```
+//!
+//! The sequence before the arrow lists the calls from the opening bid on, ignoring any
+//! passes before it, so that rules with no calls before the arrow are openings. A `*`
+//! stands for any single call. The constraints, all of which must hold, are:
+//!
+//! - `hcp` followed by a range of high card points: `12`, `12-14` or `12+`
+//! - `spades`, `hearts`, `diamonds` or `clubs` followed by a range of lengths
+//! - a shape class: `balanced`, `semi-balanced`, `single-suited`, `two-suited` or
+//!   `three-suited`
+//! - `pattern` followed by a pattern in any suits, such as `4441`
+//!
+//! and any of them can be negated with `not`. The brackets can also say whether the call
+//! is `non-forcing` (the default), `invitational`, `forcing` or `game-forcing`, which
+//! doesn't constrain the hand. Lines starting with `#` are comments. The
+//! rules are tried in order, and the first whose sequence matches the auction, whose
+//! constraints the hand satisfies and whose call is legal is chosen.
+
+use std::{fmt::Display, ops::RangeInclusive, str::FromStr};
+
+use crate::{
+    auction::{Auction, AuctionBid, Seat},
+    bidder::Bidder,
+    card::Suit,
+    error::{SystemError, SystemErrorKind},
+    hand::Hand,
+    shape::{Pattern, ShapeClass},
+};
+
+/// Whether a call obliges partner to bid again.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Force {
+    #[default]
+    NonForcing,
+    Invitational,
+    Forcing,
+    GameForcing,
+}
+
+impl Display for Force {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::NonForcing => "non-forcing",
+            Self::Invitational => "invitational",
+            Self::Forcing => "forcing",
+            Self::GameForcing => "game-forcing",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Constraint {
+    Hcp(RangeInclusive<u8>),
+    Length(Suit, RangeInclusive<usize>),
+    Class(ShapeClass),
+    Pattern(Pattern),
+    Not(Box<Constraint>),
+}
+
+impl Constraint {
+    pub(crate) fn holds(&self, hand: &Hand) -> bool {
+        match self {
+            Self::Hcp(range) => range.contains(&hand.hcp()),
+            Self::Length(suit, range) => range.contains(&hand.suit(*suit).len()),
+            Self::Class(class) => hand.shape().class() == *class,
+            Self::Pattern(pattern) => hand.shape().pattern() == *pattern,
+            Self::Not(constraint) => !constraint.holds(hand),
+        }
+    }
+}
+
+impl FromStr for Constraint {
+    type Err = SystemErrorKind;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+        let constraint = match words.as_slice() {
+            ["not", rest @ ..] => Self::Not(Box::new(rest.join(" ").parse()?)),
+            ["hcp", range] => Self::Hcp(range_of(range)?),
+            ["pattern", pattern] => Self::Pattern(
+                pattern
+                    .parse()
+                    .map_err(|_| SystemErrorKind::ConstraintNotValid)?,
+            ),
+            [suit @ ("spades" | "hearts" | "diamonds" | "clubs"), range] => {
+                Self::Length(suit.parse().unwrap(), range_of(range)?)
+            }
+            [class] => Self::Class(
+                [
+                    ShapeClass::Balanced,
+                    ShapeClass::SemiBalanced,
+                    ShapeClass::SingleSuited,
+                    ShapeClass::TwoSuited,
+                    ShapeClass::ThreeSuited,
+                ]
+                .into_iter()
+                .find(|other| other.to_string() == *class)
+                .ok_or(SystemErrorKind::ConstraintNotValid)?,
+            ),
+            _ => return Err(SystemErrorKind::ConstraintNotValid),
+        };
+        Ok(constraint)
+    }
+}
+
+/// Parses a range such as `12`, `12-14` or `12+`.
+fn range_of<T: FromStr + From<u8> + Copy>(s: &str) -> Result<RangeInclusive<T>, SystemErrorKind> {
+    let number = |s: &str| s.parse().map_err(|_| SystemErrorKind::ConstraintNotValid);
+    if let Some(min) = s.strip_suffix('+') {
+        Ok(number(min)?..=T::from(u8::MAX))
+    } else if let Some((min, max)) = s.split_once('-') {
+        Ok(number(min)?..=number(max)?)
+    } else {
+        let exact = number(s)?;
+        Ok(exact..=exact)
+    }
+}
+
+/// A rule of a bidding system: a call to make after a sequence of calls, if the hand
+/// satisfies the constraints.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rule {
+    /// The calls from the opening bid on, where `None` stands for any call.
+    sequence: Vec<Option<AuctionBid>>,
+    call: AuctionBid,
+    constraints: Vec<Constraint>,
+    force: Force,
+    explanation: Option<String>,
+}
+
+impl Rule {
+    pub fn call(&self) -> AuctionBid {
+        self.call
+    }
+
+    pub fn force(&self) -> Force {
+        self.force
+    }
+
+    pub fn explanation(&self) -> Option<&str> {
+        self.explanation.as_deref()
+    }
+
+    pub(crate) fn constraints(&self) -> &[Constraint] {
+        &self.constraints
+    }
+
+    /// Returns whether the rule's sequence matches the calls from the opening bid on.
+    fn follows(&self, calls: &[AuctionBid]) -> bool {
+        self.sequence.len() == calls.len()
+            && self
+                .sequence
+                .iter()
+                .zip(calls)
+                .all(|(expected, call)| expected.is_none_or(|expected| expected == *call))
+    }
+
+    pub fn applies(&self, hand: &Hand) -> bool {
+        self.constraints
+            .iter()
+            .all(|constraint| constraint.holds(hand))
+    }
+}
+
+impl FromStr for Rule {
+    type Err = SystemErrorKind;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (sequence, rest) = s.split_once("->").ok_or(SystemErrorKind::ArrowMissing)?;
+        let sequence: Vec<Option<AuctionBid>> = sequence
+            .split_whitespace()
+            .map(|call| match call {
+                "*" => Ok(None),
+                _ => call
+                    .parse()
+                    .map(Some)
+                    .map_err(|_| SystemErrorKind::CallNotValid),
+            })
+            .collect::<Result<_, _>>()?;
+
+        let rest = rest.trim_start();
+        let (call, mut rest) = rest.split_at(rest.find([' ', '[', '"']).unwrap_or(rest.len()));
+        let call: AuctionBid = call.parse().map_err(|_| SystemErrorKind::CallNotValid)?;
+
+        // Unless a call could be anything, the rule's calls must make a legal auction.
+        if sequence.iter().all(Option::is_some) {
+            let mut auction = Auction::new(Seat::North);
+            for call in sequence.iter().flatten().chain([&call]) {
+                auction
+                    .enter_bid(*call)
+                    .map_err(|_| SystemErrorKind::CallNotLegal)?;
+            }
+        }
+
+        let mut constraints = vec![];
+        let mut force = Force::default();
+        rest = rest.trim();
+        if let Some(inner) = rest.strip_prefix('[') {
+            let (inner, after) = inner
+                .split_once(']')
+                .ok_or(SystemErrorKind::ConstraintNotValid)?;
+            for term in inner.split(',').map(str::trim) {
+                match [
+                    Force::NonForcing,
+                    Force::Invitational,
+                    Force::Forcing,
+                    Force::GameForcing,
+                ]
+                .into_iter()
+                .find(|other| other.to_string() == term)
+                {
+                    Some(other) => force = other,
+                    None => constraints.push(term.parse()?),
+                }
+            }
+            rest = after.trim();
+        }
+
+        let explanation = match rest {
+            "" => None,
+            _ => Some(
+                rest.strip_prefix('"')
+                    .and_then(|rest| rest.strip_suffix('"'))
+                    .ok_or(SystemErrorKind::ExplanationNotValid)?
+                    .to_string(),
+            ),
+        };
+
+        Ok(Self {
+            sequence,
+            call,
+            constraints,
+            force,
+            explanation,
+        })
+    }
+}
+
+/// A bidding system, as rules read from the text format described in the module
+/// documentation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct System {
+    rules: Vec<Rule>,
+}
+
+impl FromStr for System {
+    type Err = SystemError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let rules = s
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.trim_start().starts_with('#'))
+            .map(|(idx, line)| {
+                line.parse().map_err(|kind| SystemError {
+                    line: idx + 1,
+                    kind,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { rules })
+    }
+}
+
+impl System {
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    /// Returns the rules whose sequences match the auction so far, in order.
+    pub fn candidates<'a>(&'a self, auction: &'a Auction) -> impl Iterator<Item = &'a Rule> {
+        self.candidates_after(auction.sequence())
+    }
+
+    /// Returns the rules whose sequences match the given calls, in order.
+    pub(crate) fn candidates_after<'a>(
+        &'a self,
+        calls: &'a [AuctionBid],
+    ) -> impl Iterator<Item = &'a Rule> {
+        let opening = calls
+            .iter()
+            .position(|call| *call != AuctionBid::Pass)
+            .unwrap_or(calls.len());
+        self.rules
+            .iter()
+            .filter(move |rule| rule.follows(&calls[opening..]))
+    }
+}
+
+impl Bidder for System {
+    fn bid(&self, hand: &Hand, auction: &Auction) -> AuctionBid {
+        self.candidates(auction)
+            .filter(|rule| rule.applies(hand))
+            .map(|rule| rule.call)
+            .find(|call| auction.clone().enter_bid(*call).is_ok())
+            .unwrap_or(AuctionBid::Pass)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SYSTEM: &str = "\
+# Openings
+-> 1NT [hcp 15-17, balanced] \"15-17 points, balanced\"
+-> 1S [hcp 12-21, spades 5+] \"five or more spades\"
+-> 1C [hcp 12-21]
+
+# Responses
+1NT P -> 2C [hcp 8+, not balanced, forcing] \"Stayman\"
+1NT P -> 3NT [hcp 10-15]
+1S * -> 2S [hcp 6-9, spades 3+] \"simple raise\"
+";
+
+    fn hand(hand: &str) -> Hand {
+        hand.parse().unwrap()
+    }
+
+    fn auction(calls: &str) -> Auction {
+        let mut auction = Auction::new(Seat::North);
+        for call in calls.split_whitespace() {
+            auction.enter_bid(call.parse().unwrap()).unwrap();
+        }
+        auction
+    }
+
+    #[test]
+    fn bidding() {
+        let system: System = SYSTEM.parse().unwrap();
+        assert_eq!(6, system.rules().len());
+
+        let bid = |cards, calls| system.bid(&hand(cards), &auction(calls)).to_string();
+        assert_eq!("1NT", bid("AQ52.K84.KJ3.Q92", ""));
+        assert_eq!("1♠", bid("AKJ52.K84.Q3.972", "Pass Pass"));
+        assert_eq!("1♣", bid("AK52.K84.Q3.J972", ""));
+        assert_eq!("Pass", bid("Q52.K84.J93.9732", ""));
+        assert_eq!("2♣", bid("KJ52.K8.Q3.J9742", "1NT Pass"));
+        assert_eq!("3NT", bid("KJ5.K84.Q32.J972", "1NT Pass"));
+        assert_eq!("2♠", bid("K52.Q84.J932.972", "1S Dbl"));
+        // The raise would be insufficient over the overcall.
+        assert_eq!("Pass", bid("K52.Q84.J932.972", "1S 3C"));
+    }
+
+    #[test]
+    fn explanations() {
+        let system: System = SYSTEM.parse().unwrap();
+        let auction = auction("1NT Pass");
+        let candidates: Vec<_> = system
+            .candidates(&auction)
+            .map(|rule| (rule.call().to_string(), rule.explanation()))
+            .collect();
+        assert_eq!(
+            vec![
+                ("2♣".to_string(), Some("Stayman")),
+                ("3NT".to_string(), None)
+            ],
+            candidates
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let error = |system: &str| system.parse::<System>().unwrap_err();
+        assert_eq!(
+            SystemError {
+                line: 2,
+                kind: SystemErrorKind::ArrowMissing
+            },
+            error("-> 1C\n1C P 1H\n")
+        );
+        assert_eq!(SystemErrorKind::CallNotValid, error("-> 1Z").kind);
+        assert_eq!(SystemErrorKind::CallNotValid, error("1C Q -> 1H").kind);
+        assert_eq!(SystemErrorKind::CallNotLegal, error("1H P -> 1C").kind);
+        assert_eq!(SystemErrorKind::CallNotLegal, error("1H -> XX").kind);
+        assert_eq!(
+            SystemErrorKind::ConstraintNotValid,
+            error("-> 1C [hcp]").kind
+        );
+        assert_eq!(
+            SystemErrorKind::ConstraintNotValid,
+            error("-> 1C [long]").kind
+        );
+        assert_eq!(
+            SystemErrorKind::ConstraintNotValid,
+            error("-> 1C [hcp 12").kind
+        );
+        assert_eq!(
+            SystemErrorKind::ExplanationNotValid,
+            error("-> 1C clubs").kind
+        );
+        assert!("* * -> Pass [pattern 4441, not spades 4]"
+            .parse::<System>()
+            .is_ok());
+    }
+}
@@ -1,15 +1,56 @@
 use std::io;
 
 use model::Model;
+use parker::{generator::DealGenerator, script::Script};
 use update::handle_event;
 
 pub mod model;
 pub mod update;
 pub mod view;
 
+/// Prints `count` deals satisfying the given constraint script, pointing at the offending
+/// column if the script doesn't parse.
+fn deal(count: &str, script: &str) -> Result<(), String> {
+    let count: usize = count
+        .parse()
+        .map_err(|_| format!("not a number of deals: {count}"))?;
+    let script = script
+        .parse::<Script>()
+        .map_err(|err| format!("{script}\n{:>width$}\n{err}", "^", width = err.column))?;
+
+    let generator = DealGenerator::new().deal(move |hands| script.matches(hands));
+    for number in 1..=count {
+        let hands = generator.generate().map_err(|err| format!("{:?}", err))?;
+        println!("Deal {number}\n{hands}\n");
+    }
+    Ok(())
+}
+
 fn main() -> io::Result<()> {
+    let args: Vec<String> = std::env::args().collect();
+    // `deal <count> <script>` prints deals matching a constraint script, without the TUI.
+    if let [_, command, count, script] = args.as_slice() {
+        if command == "deal" {
+            if let Err(message) = deal(count, script) {
+                eprintln!("{message}");
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+    }
+
+    // A path to a LIN file may be given, to replay a hand from Bridge Base Online.
+    let mut model = match args.get(1) {
+        Some(path) => {
+            let invalid = |err| io::Error::new(io::ErrorKind::InvalidData, err);
+            let record = std::fs::read_to_string(path)?
+                .parse()
+                .map_err(|err| invalid(format!("{:?}", err)))?;
+            Model::from_lin(record).map_err(|err| invalid(format!("{:?}", err)))?
+        }
+        None => Model::new(),
+    };
     let mut terminal = ratatui::init();
-    let mut model = Model::new();
 
     while !model.exit {
         terminal.draw(|frame| view::view(&model, frame))?;
@@ -1,23 +1,44 @@
+use std::collections::BTreeMap;
+
 use parker::{
     auction::{Auction, AuctionBid, Seat},
+    bidder::{Bidder, StandardAmerican},
+    board::Board,
+    card::Card,
     deck::Deck,
-    error::ParseError,
+    error::{BidError, ParseError, PlayError},
     hand::Hand,
+    lin::Record,
+    play::Play,
+    player::{CardPlayer, Robot},
 };
 
 #[derive(Debug)]
 pub struct Model {
+    pub board: Board,
     pub auction: Auction,
+    /// Explanations of alerted calls, keyed by the call's position in the auction.
+    pub alerts: BTreeMap<usize, String>,
     pub hands: [Hand; 4],
+    /// The seat the user bids for; robots bid for the others.
+    pub player: Seat,
+    pub bidder: StandardAmerican,
+    /// The play of the cards, once the auction closes with a contract.
+    pub play: Option<Play>,
+    pub card_player: Robot,
     pub typed: String,
     pub parsed_bid: Option<Result<AuctionBid, ParseError>>,
+    pub bid_error: Option<BidError>,
+    pub parsed_card: Option<Result<Card, ParseError>>,
+    pub play_error: Option<PlayError>,
     pub exit: bool,
 }
 
 impl Default for Model {
     /// Returns a new Model.
     /// **NOTE** that this implementation is not deterministic and uses RNG both to
-    /// shuffle the initial deck, and to pick the dealer.
+    /// shuffle the initial deck, and to pick the board (and so the dealer and
+    /// vulnerability).
     fn default() -> Self {
         Self::new()
     }
@@ -28,12 +49,131 @@ impl Model {
         let mut deck = Deck::default();
         deck.shuffle();
 
-        Self {
-            auction: Auction::new(Seat::from_repr(rand::random_range(0..3)).unwrap()),
-            hands: deck.deal(),
+        let board = Board::new(rand::random_range(1..=16));
+
+        let mut model = Self {
+            board,
+            auction: board.auction(),
+            alerts: BTreeMap::new(),
+            hands: deck.deal().into(),
+            player: Seat::South,
+            bidder: StandardAmerican,
+            play: None,
+            card_player: Robot::default(),
             typed: String::default(),
             parsed_bid: None,
+            bid_error: None,
+            parsed_card: None,
+            play_error: None,
             exit: false,
+        };
+        model.bid_robots();
+        model
+    }
+
+    /// Returns a Model replaying a hand record from Bridge Base Online, with the
+    /// record's auction, alerts and cards played so far. Fails if the recorded cards
+    /// can't be played.
+    pub fn from_lin(record: Record) -> Result<Self, PlayError> {
+        let play = record.replay().transpose()?;
+        let mut board = Board::new(record.board.unwrap_or(1));
+        board.dealer = record.dealer;
+        if let Some(vulnerability) = record.vulnerability {
+            board.vulnerability = vulnerability;
+        }
+
+        let mut model = Self {
+            board,
+            auction: record.auction,
+            alerts: record.alerts,
+            hands: record.hands,
+            player: Seat::South,
+            bidder: StandardAmerican,
+            play,
+            card_player: Robot::default(),
+            typed: String::default(),
+            parsed_bid: None,
+            bid_error: None,
+            parsed_card: None,
+            play_error: None,
+            exit: false,
+        };
+        // The player and the robots take over after the recorded calls and cards.
+        model.bid_robots();
+        model.play_robots();
+        Ok(model)
+    }
+
+    /// Enters the robots' calls until it's the player's turn or the auction closes, and
+    /// then begins the play if there's a contract.
+    pub fn bid_robots(&mut self) {
+        while !self.auction.closed() && self.auction.turn() != self.player {
+            let hand = &self.hands[self.auction.turn() as usize];
+            let call = self.bidder.bid(hand, &self.auction);
+            // The bidder only makes legal calls, so entering them can't fail.
+            let _ = self.auction.enter_bid(call);
+        }
+
+        if let Some(contract) = self.auction.contract() {
+            if self.play.is_none() {
+                self.play = Some(Play::new(contract, self.hands.clone()));
+                self.play_robots();
+            }
         }
     }
+
+    /// Returns whether the player chooses the cards for the given seat: their own, and
+    /// both their side's when it declares.
+    pub fn controls(&self, seat: Seat) -> bool {
+        let declaring = self
+            .play
+            .as_ref()
+            .is_some_and(|play| play.declarer().side() == self.player.side());
+        seat == self.player || (declaring && seat.side() == self.player.side())
+    }
+
+    /// Plays the robots' cards until it's the player's turn or the play finishes.
+    pub fn play_robots(&mut self) {
+        while let Some(play) = &self.play {
+            if play.is_finished() || self.controls(play.turn()) {
+                break;
+            }
+            let card = self.card_player.play(play);
+            // The robot only plays legal cards, so playing them can't fail.
+            let _ = self.play.as_mut().unwrap().play_card(card);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const BOARD_ONE: &str = "pn|Alice,Bob,Carol,Dave|st||\
+        md|3S876HT9876D8765CJ,S543H32D432CT9543,SAKQ2HJ54DT9C8762,|rh||ah|Board 1|sv|o|\
+        mb|p|mb|2C!|an|strong, artificial|mb|p|mb|2D|mb|p|mb|3N|mb|p|mb|p|mb|p|\
+        pc|S8|pc|S5|pc|SA|pc|S9|";
+
+    #[test]
+    fn from_lin_keeps_the_alerts() {
+        let model = Model::from_lin(BOARD_ONE.parse().unwrap()).unwrap();
+        assert_eq!(
+            Some(&"strong, artificial".to_string()),
+            model.alerts.get(&1)
+        );
+    }
+
+    #[test]
+    fn from_lin_keeps_the_play() {
+        let model = Model::from_lin(BOARD_ONE.parse().unwrap()).unwrap();
+
+        // North wins the recorded trick and leads for the robots, then it's South's turn.
+        let play = model.play.unwrap();
+        let recorded: Vec<Card> = ["8S", "5S", "AS", "9S"]
+            .map(|card| card.parse().unwrap())
+            .into();
+        assert_eq!(recorded, play.tricks()[0].cards());
+        assert_eq!(2, play.current_trick().cards().len());
+        assert_eq!(Seat::South, play.turn());
+    }
 }
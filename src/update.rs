@@ -1,7 +1,7 @@
 use std::io;
 
 use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
-use parker::auction::AuctionBid;
+use parker::{auction::AuctionBid, card::Card};
 
 use crate::model::Model;
 
@@ -12,6 +12,7 @@ pub enum Message {
     Backspace,
     Enter,
     Bid(AuctionBid),
+    Card(Card),
 }
 
 pub fn handle_event(_model: &Model) -> io::Result<Option<Message>> {
@@ -53,6 +54,14 @@ pub fn update(model: &mut Model, message: Message) -> Option<Message> {
         Message::Backspace => {
             model.typed.pop();
         }
+        Message::Enter if model.play.is_some() => {
+            model.parsed_card = Some(model.typed.to_uppercase().parse::<Card>());
+            model.typed = "".to_string();
+
+            if let Some(Ok(card)) = model.parsed_card {
+                return Some(Message::Card(card));
+            }
+        }
         Message::Enter => {
             model.parsed_bid = Some(model.typed.parse::<AuctionBid>());
             model.typed = "".to_string();
@@ -62,7 +71,18 @@ pub fn update(model: &mut Model, message: Message) -> Option<Message> {
             }
         }
         Message::Bid(auction_bid) => {
-            model.auction.sequence.push(auction_bid);
+            model.bid_error = model.auction.enter_bid(auction_bid).err();
+            if model.bid_error.is_none() {
+                model.bid_robots();
+            }
+        }
+        Message::Card(card) => {
+            if let Some(play) = &mut model.play {
+                model.play_error = play.play_card(card).err();
+                if model.play_error.is_none() {
+                    model.play_robots();
+                }
+            }
         }
     }
     None
@@ -1,6 +1,9 @@
 use std::rc::Rc;
 
-use parker::auction::{AuctionBid, ContractBid};
+use parker::auction::{AuctionBid, ContractBid, Seat, Side};
+use parker::card::Suit;
+use parker::hand::Hand;
+use parker::play::Play;
 use ratatui::layout::{Alignment, Constraint, Direction, Layout, Position, Rect};
 use ratatui::widgets::Wrap;
 use ratatui::Frame;
@@ -30,7 +33,7 @@ pub fn view(model: &Model, frame: &mut Frame) {
     view_auction_area(model, frame, display_chunks[0]);
     view_typing_area(model, frame, display_chunks[1]);
 
-    view_control_instructions(frame, chunks[1]);
+    view_control_instructions(model, frame, chunks[1]);
 }
 
 fn view_auction_area(model: &Model, frame: &mut Frame, display_area: Rect) {
@@ -51,17 +54,108 @@ fn view_auction_area(model: &Model, frame: &mut Frame, display_area: Rect) {
         })
         .collect();
 
-    let title = Line::from(" Auction ".bold());
+    let title = Line::from(
+        format!(
+            " Board {} | Dealer {} | Vul {} ",
+            model.board.number,
+            char::from(model.board.dealer),
+            model.board.vulnerability
+        )
+        .bold(),
+    );
     let block = Block::bordered()
         .title(title.centered())
         .border_set(border::THICK);
 
-    let auction_display = model.auction.to_string();
-    let auction =
-        Paragraph::new(auction_display.lines().map(Line::from).collect::<Vec<_>>()).centered();
+    // Once the play begins, the centre shows the trick in progress instead of the auction.
+    let centre_display = match &model.play {
+        Some(play) => trick_display(play),
+        None => auction_display(model),
+    };
+    let centre =
+        Paragraph::new(centre_display.lines().map(Line::from).collect::<Vec<_>>()).centered();
 
     frame.render_widget(block, display_area);
-    frame.render_widget(auction, rects[1][1]);
+    frame.render_widget(centre, rects[1][1]);
+
+    for (seat, rect) in [
+        (Seat::North, rects[1][0]),
+        (Seat::East, rects[2][1]),
+        (Seat::South, rects[1][2]),
+        (Seat::West, rects[0][1]),
+    ] {
+        // The robots' hands stay hidden until the auction is passed out or the play
+        // finishes, except for dummy's once the opening lead is made.
+        match &model.play {
+            Some(play) => {
+                let led = !play.tricks().is_empty() || !play.current_trick().cards().is_empty();
+                if model.controls(seat) || (seat == play.dummy() && led) || play.is_finished() {
+                    view_hand(play.hand(seat), frame, rect);
+                }
+            }
+            None => {
+                if seat == model.player || model.auction.closed() {
+                    view_hand(&model.hands[seat as usize], frame, rect);
+                }
+            }
+        }
+    }
+}
+
+/// Shows the auction, with the explanations of any alerted calls underneath.
+fn auction_display(model: &Model) -> String {
+    let mut lines = vec![model.auction.to_string()];
+    for (idx, explanation) in &model.alerts {
+        if let Some(call) = model.auction.sequence().get(*idx) {
+            lines.push(format!("{}! {}", call, explanation).trim_end().to_string());
+        }
+    }
+    lines.join("\n")
+}
+
+/// Describes the contract, the cards played to the current trick (or to the last one,
+/// between tricks) and the tricks each side has won.
+fn trick_display(play: &Play) -> String {
+    let trick = match play.current_trick().cards().is_empty() {
+        true => play.tricks().last().unwrap_or(play.current_trick()),
+        false => play.current_trick(),
+    };
+    let mut lines = vec![format!("Contract {}", play.contract()), "".to_string()];
+    lines.extend(
+        trick
+            .plays()
+            .map(|(seat, card)| format!("{} {}", char::from(seat), card)),
+    );
+    lines.push("".to_string());
+    lines.push(format!(
+        "NS {} | EW {}",
+        play.tricks_won(Side::NorthSouth),
+        play.tricks_won(Side::EastWest)
+    ));
+    lines.join("\n")
+}
+
+/// Renders a hand suit by suit, with a summary of its points and shape underneath.
+fn view_hand(hand: &Hand, frame: &mut Frame, area: Rect) {
+    let mut lines: Vec<Line> = [Suit::Spades, Suit::Hearts, Suit::Diamonds, Suit::Clubs]
+        .into_iter()
+        .map(|suit| Line::from(hand.display_suit(suit)))
+        .collect();
+
+    let shape = hand.shape();
+    lines.push(Line::from(""));
+    lines.push(
+        Line::from(format!(
+            "{} HCP, {} {}",
+            hand.hcp(),
+            shape.pattern(),
+            shape.class()
+        ))
+        .dark_gray(),
+    );
+
+    let hand = Paragraph::new(lines).alignment(Alignment::Center);
+    frame.render_widget(hand, area);
 }
 
 fn view_typing_area(model: &Model, frame: &mut Frame, display_area: Rect) {
@@ -70,23 +164,40 @@ fn view_typing_area(model: &Model, frame: &mut Frame, display_area: Rect) {
         .constraints([Constraint::Fill(1), Constraint::Max(3)])
         .split(display_area);
 
-    let result_display = Paragraph::new(Line::from(match model.parsed_bid {
-        Some(Ok(bid)) => vec![
-            "You bid ".into(),
-            match bid {
-                AuctionBid::Bid(ContractBid { level, suit }) => format!("{} {:?}", level, suit),
-                AuctionBid::Pass => "Pass".to_string(),
-                AuctionBid::Double => "Double".to_string(),
-                AuctionBid::Redouble => "Redouble".to_string(),
-            }
-            .bold(),
-            format!(" ({})", bid).into(),
-        ],
-        Some(Err(err)) => vec!["Parsing error: ".into(), format!("{:?}", err).bold()],
-        None => vec!["".into()],
-    }))
-    .wrap(Wrap { trim: true })
-    .block(Block::bordered().title("Output"));
+    let result_display = if model.play.is_some() {
+        Line::from(match (model.parsed_card, model.play_error) {
+            (Some(Ok(card)), Some(err)) => vec![
+                format!("Illegal card ({}): ", card).into(),
+                format!("{:?}", err).bold(),
+            ],
+            (Some(Ok(card)), None) => vec!["You played ".into(), card.to_string().bold()],
+            (Some(Err(err)), _) => vec!["Parsing error: ".into(), format!("{:?}", err).bold()],
+            (None, _) => vec!["".into()],
+        })
+    } else {
+        Line::from(match (model.parsed_bid, model.bid_error) {
+            (Some(Ok(bid)), Some(err)) => vec![
+                format!("Illegal bid ({}): ", bid).into(),
+                format!("{:?}", err).bold(),
+            ],
+            (Some(Ok(bid)), None) => vec![
+                "You bid ".into(),
+                match bid {
+                    AuctionBid::Bid(ContractBid { level, suit }) => format!("{} {:?}", level, suit),
+                    AuctionBid::Pass => "Pass".to_string(),
+                    AuctionBid::Double => "Double".to_string(),
+                    AuctionBid::Redouble => "Redouble".to_string(),
+                }
+                .bold(),
+                format!(" ({})", bid).into(),
+            ],
+            (Some(Err(err)), _) => vec!["Parsing error: ".into(), format!("{:?}", err).bold()],
+            (None, _) => vec!["".into()],
+        })
+    };
+    let result_display = Paragraph::new(result_display)
+        .wrap(Wrap { trim: true })
+        .block(Block::bordered().title("Output"));
     frame.render_widget(result_display, rows[0]);
 
     let parsing_window = Paragraph::new(Line::from(format!("> {}", model.typed)))
@@ -99,14 +210,17 @@ fn view_typing_area(model: &Model, frame: &mut Frame, display_area: Rect) {
     ));
 }
 
-fn view_control_instructions(frame: &mut Frame, display_area: Rect) {
+fn view_control_instructions(model: &Model, frame: &mut Frame, display_area: Rect) {
     let instructions = Paragraph::new(Line::from(vec![
         "<Ctrl-c>".dark_gray().bold(),
         " Quit".dark_gray(),
         " | <Ctrl-u>".dark_gray().bold(),
         " Clear".dark_gray(),
         " | <Enter>".dark_gray().bold(),
-        " Submit bid".dark_gray(),
+        match model.play {
+            Some(_) => " Play card".dark_gray(),
+            None => " Submit bid".dark_gray(),
+        },
     ]))
     .alignment(Alignment::Right);
     frame.render_widget(instructions, display_area);